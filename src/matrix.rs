@@ -1,361 +1,442 @@
 use crate::exporters::excalidraw::{Drawable, Element, ExcalidrawFile};
 use std::fmt::Display;
+use with_result::{cramer::Cramer, determinant::DeterminantExpansion, LineMatrix};
+
+pub mod complex;
+pub mod rational;
+pub mod scalar;
 
 pub mod with_result {
-    use crate::exporters::excalidraw::{Drawable, Element, ExcalidrawFile};
+    use crate::exporters::excalidraw::{Drawable, ExcalidrawFile};
     use std::{
         fmt::{Debug, Display},
         ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign},
     };
 
+    pub mod condition;
+    pub mod cramer;
+    pub mod determinant;
+    pub mod dynamic;
+    pub mod eigen;
+    pub mod elimination;
+    pub mod equations;
+    pub mod error;
+    pub mod history;
+    pub mod inverse;
+    pub mod least_squares;
+    pub mod lu;
+    pub mod notation;
+    pub mod qr;
+    pub mod solution;
+    pub mod space;
+    pub mod variable;
+
+    use super::{rational::Rational, scalar::Scalar};
+    use elimination::RowOperation;
+    use error::RowOperationError;
+    use variable::Variable;
+
     /// A square matrix with arbitrary size and a result column
     ///
     /// **Indexing is 1-based**
+    pub type LineMatrix<const SIZE: usize, T = f64> = AugmentedMatrix<SIZE, SIZE, 1, T>;
+
+    /// A matrix with `ROWS` lines of `COLS` coefficients and `RESULTS` result columns
+    ///
+    /// The coefficient columns can be labelled with the variable they belong to, see
+    /// [`AugmentedMatrix::with_labels`]
+    ///
+    /// **Indexing is 1-based**
     #[derive(Clone, Copy)]
-    #[repr(transparent)]
-    pub struct LineMatrix<const SIZE: usize>([Line<SIZE>; SIZE]);
+    pub struct AugmentedMatrix<const ROWS: usize, const COLS: usize, const RESULTS: usize = 1, T = f64>(
+        [Line<COLS, RESULTS, T>; ROWS],
+        Option<[Variable; COLS]>,
+    );
 
-    impl<const SIZE: usize> LineMatrix<SIZE> {
-        pub fn new(matrix: [[f64; SIZE]; SIZE], result: [f64; SIZE]) -> Self {
-            let mut lines = [Line::new(matrix[0], result[0]); SIZE];
-            for i in 0..SIZE {
-                lines[i] = Line::new(matrix[i], result[i]);
-            }
-            Self(lines)
+    impl<const ROWS: usize, const COLS: usize, T: Scalar> AugmentedMatrix<ROWS, COLS, 1, T> {
+        pub fn new(matrix: [[T; COLS]; ROWS], result: [T; ROWS]) -> Self {
+            Self(
+                std::array::from_fn(|i| Line::new(matrix[i], result[i])),
+                None,
+            )
         }
+    }
 
-        pub fn mul(mut self, line: usize, value: f64) -> Self {
-            if !(1..=SIZE).contains(&line) {
-                panic!("Invalid line");
-            }
-            self.0[line - 1] *= value;
-            self
+    impl<const ROWS: usize, const COLS: usize> AugmentedMatrix<ROWS, COLS, 1, Rational> {
+        /// Builds an exact matrix from integers, so elimination never rounds
+        pub fn from_integers(matrix: [[i64; COLS]; ROWS], result: [i64; ROWS]) -> Self {
+            Self::new(
+                matrix.map(|line| line.map(Rational::from)),
+                result.map(Rational::from),
+            )
         }
+    }
 
-        pub fn div(mut self, line: usize, value: f64) -> Self {
-            if !(1..=SIZE).contains(&line) {
-                panic!("Invalid line");
-            }
-            self.0[line - 1] /= value;
-            self
+    impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+        AugmentedMatrix<ROWS, COLS, RESULTS, T>
+    {
+        pub fn with_results(matrix: [[T; COLS]; ROWS], results: [[T; RESULTS]; ROWS]) -> Self {
+            Self(
+                std::array::from_fn(|i| Line::with_results(matrix[i], results[i])),
+                None,
+            )
         }
 
-        pub fn add_to(mut self, line: usize, target_line: usize) -> Self {
-            if !(1..=SIZE).contains(&line) {
-                panic!("Invalid line");
-            }
-            if !(1..=SIZE).contains(&target_line) {
-                panic!("Invalid target line");
-            }
-            self.0[target_line - 1] += self.0[line - 1];
-            self
+        /// Converts every entry, e.g. `matrix.map(f64::from)` to approximate a rational matrix
+        pub fn map<U: Scalar>(
+            self,
+            mut f: impl FnMut(T) -> U,
+        ) -> AugmentedMatrix<ROWS, COLS, RESULTS, U> {
+            AugmentedMatrix(
+                self.0
+                    .map(|line| Line(line.0.map(&mut f), line.1.map(&mut f))),
+                self.1,
+            )
+        }
+
+        /// The coefficients with lines and columns swapped, dropping the results
+        pub fn transpose(&self) -> AugmentedMatrix<COLS, ROWS, 0, T> {
+            AugmentedMatrix(
+                std::array::from_fn(|col| {
+                    Line(std::array::from_fn(|line| self.0[line].0[col]), [])
+                }),
+                None,
+            )
+        }
+
+        pub fn mul(self, line: usize, value: T) -> Self {
+            RowOperation::Mul { line, value }.apply(self)
+        }
+
+        /// Divides a line by `value`, panicking if it is zero
+        ///
+        /// Values close to zero are accepted, [`History`](history::History)
+        /// reports them as [`SmallPivot`](condition::SmallPivot) warnings
+        pub fn div(self, line: usize, value: T) -> Self {
+            RowOperation::Div { line, value }.apply(self)
+        }
+
+        pub fn swap(self, line: usize, other_line: usize) -> Self {
+            RowOperation::Swap { line, other_line }.apply(self)
         }
 
-        pub fn sub_to(mut self, line: usize, target_line: usize) -> Self {
-            if !(1..=SIZE).contains(&line) {
-                panic!("Invalid line");
+        /// Swaps two coefficient columns, which swaps the variables they belong to
+        pub fn swap_columns(self, column: usize, other_column: usize) -> Self {
+            RowOperation::SwapColumns {
+                column,
+                other_column,
             }
-            if !(1..=SIZE).contains(&target_line) {
-                panic!("Invalid target line");
+            .apply(self)
+        }
+
+        pub fn add_to(self, line: usize, target_line: usize) -> Self {
+            RowOperation::AddTo { line, target_line }.apply(self)
+        }
+
+        pub fn sub_to(self, line: usize, target_line: usize) -> Self {
+            RowOperation::SubTo { line, target_line }.apply(self)
+        }
+
+        pub fn mul_to(self, line: usize, target_line: usize, value: T) -> Self {
+            RowOperation::MulTo {
+                line,
+                target_line,
+                value,
             }
-            self.0[target_line - 1] -= self.0[line - 1];
-            self
+            .apply(self)
         }
 
-        pub fn mul_to(mut self, line: usize, target_line: usize, value: f64) -> Self {
-            if !(1..=SIZE).contains(&line) {
-                panic!("Invalid line");
+        pub fn div_to(self, line: usize, target_line: usize, value: T) -> Self {
+            RowOperation::DivTo {
+                line,
+                target_line,
+                value,
             }
-            if !(1..=SIZE).contains(&target_line) {
-                panic!("Invalid target line");
+            .apply(self)
+        }
+
+        /// Returns the line or `None` if it is out of range
+        pub fn get(&self, line: usize) -> Option<&Line<COLS, RESULTS, T>> {
+            self.0.get(line.checked_sub(1)?)
+        }
+
+        /// Returns the line or `None` if it is out of range
+        pub fn get_mut(&mut self, line: usize) -> Option<&mut Line<COLS, RESULTS, T>> {
+            self.0.get_mut(line.checked_sub(1)?)
+        }
+
+        pub fn try_mul(self, line: usize, value: T) -> Result<Self, RowOperationError> {
+            RowOperation::Mul { line, value }.try_apply(self)
+        }
+
+        pub fn try_div(self, line: usize, value: T) -> Result<Self, RowOperationError> {
+            RowOperation::Div { line, value }.try_apply(self)
+        }
+
+        pub fn try_swap(self, line: usize, other_line: usize) -> Result<Self, RowOperationError> {
+            RowOperation::Swap { line, other_line }.try_apply(self)
+        }
+
+        pub fn try_swap_columns(
+            self,
+            column: usize,
+            other_column: usize,
+        ) -> Result<Self, RowOperationError> {
+            RowOperation::SwapColumns {
+                column,
+                other_column,
             }
-            self.0[target_line - 1] += self.0[line - 1] * value;
-            self
+            .try_apply(self)
+        }
+
+        pub fn try_add_to(
+            self,
+            line: usize,
+            target_line: usize,
+        ) -> Result<Self, RowOperationError> {
+            RowOperation::AddTo { line, target_line }.try_apply(self)
+        }
+
+        pub fn try_sub_to(
+            self,
+            line: usize,
+            target_line: usize,
+        ) -> Result<Self, RowOperationError> {
+            RowOperation::SubTo { line, target_line }.try_apply(self)
         }
 
-        pub fn div_to(mut self, line: usize, target_line: usize, value: f64) -> Self {
-            if !(1..=SIZE).contains(&line) {
-                panic!("Invalid line");
+        pub fn try_mul_to(
+            self,
+            line: usize,
+            target_line: usize,
+            value: T,
+        ) -> Result<Self, RowOperationError> {
+            RowOperation::MulTo {
+                line,
+                target_line,
+                value,
             }
-            if !(1..=SIZE).contains(&target_line) {
-                panic!("Invalid target line");
+            .try_apply(self)
+        }
+
+        pub fn try_div_to(
+            self,
+            line: usize,
+            target_line: usize,
+            value: T,
+        ) -> Result<Self, RowOperationError> {
+            RowOperation::DivTo {
+                line,
+                target_line,
+                value,
             }
-            self.0[target_line - 1] += self.0[line - 1] / value;
-            self
+            .try_apply(self)
+        }
+
+        /// The labels of the columns, empty if there are none
+        fn header(&self) -> Vec<String> {
+            self.1
+                .iter()
+                .flatten()
+                .map(|label| label.to_string())
+                .collect()
+        }
+
+        fn cells(&self, precision: usize) -> Vec<Vec<String>> {
+            self.0
+                .iter()
+                .map(|line| {
+                    line.0
+                        .iter()
+                        .chain(&line.1)
+                        .map(|&value| value.format(precision))
+                        .collect()
+                })
+                .collect()
         }
     }
 
-    impl<const SIZE: usize> Index<usize> for LineMatrix<SIZE> {
-        type Output = Line<SIZE>;
+    impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Index<usize>
+        for AugmentedMatrix<ROWS, COLS, RESULTS, T>
+    {
+        type Output = Line<COLS, RESULTS, T>;
 
         fn index(&self, index: usize) -> &Self::Output {
-            &self.0[index - 1]
+            self.get(index).expect("Invalid line")
         }
     }
 
-    impl<const SIZE: usize> IndexMut<usize> for LineMatrix<SIZE> {
+    impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> IndexMut<usize>
+        for AugmentedMatrix<ROWS, COLS, RESULTS, T>
+    {
         fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-            &mut self.0[index - 1]
+            self.get_mut(index).expect("Invalid line")
         }
     }
 
-    impl<const SIZE: usize> Debug for LineMatrix<SIZE> {
+    impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Debug
+        for AugmentedMatrix<ROWS, COLS, RESULTS, T>
+    {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             Display::fmt(&self, f)
         }
     }
 
-    impl<const SIZE: usize> Display for LineMatrix<SIZE> {
+    impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Display
+        for AugmentedMatrix<ROWS, COLS, RESULTS, T>
+    {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             let precision = f.precision().unwrap_or(3);
-            let mut col_widths = [0; SIZE];
-            let mut strings = vec![Vec::with_capacity(SIZE); SIZE];
-            for col in 0..SIZE {
-                let mut col_width = 0;
-                for row in 0..SIZE {
-                    strings[row].push({
-                        let res_string = format!("{:.1$}", self.0[row].0[col], precision);
-                        let res = res_string
-                            .as_str()
-                            .trim_end_matches('0')
-                            .trim_end_matches('.');
-                        if res == "-0" {
-                            String::from("0")
-                        } else {
-                            res.to_string()
-                        }
-                    });
-                    let len = strings[row][col].len();
-                    if len > col_width {
-                        col_width = len;
-                    }
-                }
-                col_widths[col] = col_width;
-            }
-            let mut res_strings = Vec::with_capacity(SIZE);
-            let mut res_width = 0;
-            for i in 0..SIZE {
-                res_strings.push({
-                    let res_string = format!("{:.1$}", self.0[i].1, precision);
-                    let res = res_string
-                        .as_str()
-                        .trim_end_matches('0')
-                        .trim_end_matches('.');
-                    if res == "-0" {
-                        String::from("0")
-                    } else {
-                        res.to_string()
-                    }
-                });
-                let len = res_strings[i].len();
-                if len > res_width {
-                    res_width = len;
-                }
-            }
-            let width = col_widths.iter().sum::<usize>() + res_width + 2;
-            f.write_fmt(format_args!("\n┌╴{:w$}╶┐\n", "", w = width))?;
-            for row in 0..SIZE {
-                f.write_str("│")?;
-                for col in 0..SIZE {
-                    let cw = col_widths[col];
-                    f.write_fmt(format_args!(" {:>cw$}", strings[row][col], cw = cw))?;
-                }
-                f.write_fmt(format_args!("│{:>rw$} ", res_strings[row], rw = res_width))?;
-                f.write_str("│\n")?;
-            }
-            f.write_fmt(format_args!("└╴{:w$}╶┘", "", w = width))
+            super::fmt_augmented_with_header(f, &self.header(), &self.cells(precision), COLS)
         }
     }
 
-    impl<const SIZE: usize> Drawable for LineMatrix<SIZE> {
+    impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Drawable
+        for AugmentedMatrix<ROWS, COLS, RESULTS, T>
+    {
         fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
-            let precision = 3;
-            let scale = file.app_state.grid_size;
-            file.elements.push(Element::simple_line(
+            super::draw_augmented_with_header(
+                file,
                 x,
                 y,
                 locked,
-                vec![
-                    [scale, 0],
-                    [0, 0],
-                    [0, scale * (SIZE as i32 * 2 + 1)],
-                    [scale, scale * (SIZE as i32 * 2 + 1)],
-                ],
-            ));
-            let mut col_widths = [0; SIZE];
-            let mut strings = vec![Vec::with_capacity(SIZE); SIZE];
-            for col in 0..SIZE {
-                let mut col_width = 0;
-                for row in 0..SIZE {
-                    strings[row].push({
-                        let res_string = format!("{:.1$}", self.0[row].0[col], precision);
-                        let res = res_string
-                            .as_str()
-                            .trim_end_matches('0')
-                            .trim_end_matches('.');
-                        if res == "-0" {
-                            String::from("0")
-                        } else {
-                            res.to_string()
-                        }
-                    });
-                    let len = strings[row][col].len();
-                    if len > col_width {
-                        col_width = len;
-                    }
-                }
-                col_widths[col] = col_width;
-            }
-            let mut res_strings = Vec::with_capacity(SIZE);
-            let mut res_width = 0;
-            for i in 0..SIZE {
-                res_strings.push({
-                    let res_string = format!("{:.1$}", self.0[i].1, precision);
-                    let res = res_string
-                        .as_str()
-                        .trim_end_matches('0')
-                        .trim_end_matches('.');
-                    if res == "-0" {
-                        String::from("0")
-                    } else {
-                        res.to_string()
-                    }
-                });
-                let len = res_strings[i].len() * 10;
-                if len > res_width {
-                    res_width = len;
-                }
-            }
-            let total_col_width: i32 =
-                col_widths.iter().sum::<usize>() as i32 * 10 + (SIZE as i32 - 1) * scale;
-            let mut current_offset = 0;
-            for col in 0..SIZE {
-                for row in 0..SIZE {
-                    file.elements.push(Element::draw_small_monospaced_text(
-                        x + scale + current_offset,
-                        y + scale + scale * 2 * row as i32,
-                        locked,
-                        strings[row][col].clone(),
-                    ));
-                }
-                current_offset += col_widths[col] as i32 * 10 + scale;
-            }
-            file.elements.push(Element::simple_line(
-                x + scale * 2 + total_col_width,
-                y + scale,
-                locked,
-                vec![[0, 0], [0, scale * (SIZE as i32 * 2 - 1)]],
-            ));
-            for i in 0..SIZE {
-                file.elements.push(Element::draw_small_monospaced_text(
-                    x + scale * 3 + total_col_width,
-                    y + scale + scale * 2 * i as i32,
-                    locked,
-                    res_strings[i].clone(),
-                ));
-            }
-            file.elements.push(Element::simple_line(
-                x + scale * 3 + total_col_width + res_width as i32,
-                y,
-                locked,
-                vec![
-                    [0, 0],
-                    [scale, 0],
-                    [scale, scale * (SIZE as i32 * 2 + 1)],
-                    [0, scale * (SIZE as i32 * 2 + 1)],
-                ],
-            ));
-            (
-                scale * 4 + total_col_width + res_width as i32,
-                scale * (SIZE as i32 * 2 + 1),
+                &self.header(),
+                &self.cells(3),
+                COLS,
             )
         }
     }
 
     #[derive(Clone, Copy)]
-    pub struct Line<const SIZE: usize>([f64; SIZE], f64);
+    pub struct Line<const SIZE: usize, const RESULTS: usize = 1, T = f64>([T; SIZE], [T; RESULTS]);
+
+    impl<const SIZE: usize, T: Scalar> Line<SIZE, 1, T> {
+        pub fn new(line: [T; SIZE], result: T) -> Self {
+            Self(line, [result])
+        }
+
+        pub fn result(&self) -> T {
+            self.1[0]
+        }
+    }
+
+    impl<const SIZE: usize, const RESULTS: usize, T: Scalar> Line<SIZE, RESULTS, T> {
+        pub fn with_results(line: [T; SIZE], results: [T; RESULTS]) -> Self {
+            Self(line, results)
+        }
+
+        pub fn coefficients(&self) -> &[T; SIZE] {
+            &self.0
+        }
+
+        pub fn results(&self) -> &[T; RESULTS] {
+            &self.1
+        }
+
+        pub fn is_finite(&self) -> bool {
+            self.0.iter().chain(&self.1).all(|value| value.is_finite())
+        }
+
+        /// The dot product, treating coefficients and results as one vector
+        pub fn dot(&self, other: &Self) -> T {
+            self.0
+                .iter()
+                .chain(&self.1)
+                .zip(other.0.iter().chain(&other.1))
+                .fold(T::zero(), |acc, (&a, &b)| acc + a * b)
+        }
+    }
 
-    impl<const SIZE: usize> Line<SIZE> {
-        pub fn new(line: [f64; SIZE], result: f64) -> Self {
-            Self(line, result)
+    impl<const SIZE: usize, const RESULTS: usize> Line<SIZE, RESULTS, f64> {
+        /// The euclidean length, treating coefficients and results as one vector
+        pub fn norm(&self) -> f64 {
+            self.dot(self).sqrt()
         }
     }
 
-    impl<const SIZE: usize> Add for Line<SIZE> {
+    impl<const SIZE: usize, const RESULTS: usize, T: Scalar> Add for Line<SIZE, RESULTS, T> {
         type Output = Self;
 
         fn add(mut self, rhs: Self) -> Self::Output {
             for i in 0..SIZE {
                 self.0[i] += rhs.0[i];
             }
-            self.1 += rhs.1;
+            for i in 0..RESULTS {
+                self.1[i] += rhs.1[i];
+            }
             self
         }
     }
 
-    impl<const SIZE: usize> Sub for Line<SIZE> {
+    impl<const SIZE: usize, const RESULTS: usize, T: Scalar> Sub for Line<SIZE, RESULTS, T> {
         type Output = Self;
 
         fn sub(mut self, rhs: Self) -> Self::Output {
             for i in 0..SIZE {
                 self.0[i] -= rhs.0[i];
             }
-            self.1 -= rhs.1;
+            for i in 0..RESULTS {
+                self.1[i] -= rhs.1[i];
+            }
             self
         }
     }
 
-    impl<const SIZE: usize> Mul<f64> for Line<SIZE> {
+    impl<const SIZE: usize, const RESULTS: usize, T: Scalar> Mul<T> for Line<SIZE, RESULTS, T> {
         type Output = Self;
 
-        fn mul(mut self, rhs: f64) -> Self::Output {
+        fn mul(mut self, rhs: T) -> Self::Output {
             for i in 0..SIZE {
                 self.0[i] *= rhs;
             }
-            self.1 *= rhs;
+            for value in &mut self.1 {
+                *value *= rhs;
+            }
             self
         }
     }
 
-    impl<const SIZE: usize> Div<f64> for Line<SIZE> {
+    impl<const SIZE: usize, const RESULTS: usize, T: Scalar> Div<T> for Line<SIZE, RESULTS, T> {
         type Output = Self;
 
-        fn div(mut self, rhs: f64) -> Self::Output {
+        fn div(mut self, rhs: T) -> Self::Output {
             for i in 0..SIZE {
                 self.0[i] /= rhs;
             }
-            self.1 /= rhs;
+            for value in &mut self.1 {
+                *value /= rhs;
+            }
             self
         }
     }
 
-    impl<const SIZE: usize> AddAssign for Line<SIZE> {
+    impl<const SIZE: usize, const RESULTS: usize, T: Scalar> AddAssign for Line<SIZE, RESULTS, T> {
         fn add_assign(&mut self, rhs: Self) {
-            let result = self.clone() + rhs;
+            let result = *self + rhs;
             self.0 = result.0;
             self.1 = result.1;
         }
     }
 
-    impl<const SIZE: usize> SubAssign for Line<SIZE> {
+    impl<const SIZE: usize, const RESULTS: usize, T: Scalar> SubAssign for Line<SIZE, RESULTS, T> {
         fn sub_assign(&mut self, rhs: Self) {
-            let result = self.clone() - rhs;
+            let result = *self - rhs;
             self.0 = result.0;
             self.1 = result.1;
         }
     }
 
-    impl<const SIZE: usize> MulAssign<f64> for Line<SIZE> {
-        fn mul_assign(&mut self, rhs: f64) {
-            let result = self.clone() * rhs;
+    impl<const SIZE: usize, const RESULTS: usize, T: Scalar> MulAssign<T> for Line<SIZE, RESULTS, T> {
+        fn mul_assign(&mut self, rhs: T) {
+            let result = *self * rhs;
             self.0 = result.0;
             self.1 = result.1;
         }
     }
 
-    impl<const SIZE: usize> DivAssign<f64> for Line<SIZE> {
-        fn div_assign(&mut self, rhs: f64) {
-            let result = self.clone() / rhs;
+    impl<const SIZE: usize, const RESULTS: usize, T: Scalar> DivAssign<T> for Line<SIZE, RESULTS, T> {
+        fn div_assign(&mut self, rhs: T) {
+            let result = *self / rhs;
             self.0 = result.0;
             self.1 = result.1;
         }
@@ -369,6 +450,12 @@ impl Matrix2x2 {
         Self(value, result)
     }
 
+    pub fn swap(&mut self, row: usize, other_row: usize) -> &mut Self {
+        self.0.swap(row, other_row);
+        self.1.swap(row, other_row);
+        self
+    }
+
     pub fn mul(&mut self, row: usize, value: f64) -> &mut Self {
         self.0[row][0] *= value;
         self.0[row][1] *= value;
@@ -412,6 +499,23 @@ impl Matrix2x2 {
     }
 }
 
+impl Matrix2x2 {
+    /// Calculates the determinant of the coefficients using Gaussian elimination
+    pub fn determinant(&self) -> f64 {
+        LineMatrix::new(self.0, self.1).determinant()
+    }
+
+    /// Calculates the determinant of the coefficients the way it is done by hand
+    pub fn determinant_expansion(&self) -> DeterminantExpansion {
+        LineMatrix::new(self.0, self.1).determinant_expansion()
+    }
+
+    /// Calculates every determinant of Cramer's rule
+    pub fn cramer(&self) -> Cramer<2> {
+        LineMatrix::new(self.0, self.1).cramer()
+    }
+}
+
 impl Display for Matrix2x2 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         const SIZE: usize = 2;
@@ -588,6 +692,12 @@ impl Matrix3x3 {
         Self(value, result)
     }
 
+    pub fn swap(&mut self, row: usize, other_row: usize) -> &mut Self {
+        self.0.swap(row, other_row);
+        self.1.swap(row, other_row);
+        self
+    }
+
     pub fn mul(&mut self, row: usize, value: f64) -> &mut Self {
         self.0[row][0] *= value;
         self.0[row][1] *= value;
@@ -637,6 +747,23 @@ impl Matrix3x3 {
     }
 }
 
+impl Matrix3x3 {
+    /// Calculates the determinant of the coefficients using Gaussian elimination
+    pub fn determinant(&self) -> f64 {
+        LineMatrix::new(self.0, self.1).determinant()
+    }
+
+    /// Calculates the determinant of the coefficients the way it is done by hand
+    pub fn determinant_expansion(&self) -> DeterminantExpansion {
+        LineMatrix::new(self.0, self.1).determinant_expansion()
+    }
+
+    /// Calculates every determinant of Cramer's rule
+    pub fn cramer(&self) -> Cramer<3> {
+        LineMatrix::new(self.0, self.1).cramer()
+    }
+}
+
 impl Display for Matrix3x3 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         const SIZE: usize = 3;
@@ -814,3 +941,411 @@ fn sum(arr: &[usize]) -> usize {
     // }
     // accumulator
 }
+
+/// Draws lines of text below each other, spaced like the lines of a matrix
+///
+/// Returns the width of the longest line and the height of a matrix with as many lines
+fn draw_text_lines(
+    file: &mut ExcalidrawFile,
+    x: i32,
+    y: i32,
+    locked: bool,
+    lines: Vec<String>,
+) -> (i32, i32) {
+    let scale = file.app_state.grid_size;
+    let rows = lines.len() as i32;
+    let mut width = 0;
+    for (row, line) in lines.into_iter().enumerate() {
+        width = width.max(line.chars().count() as i32 * 10);
+        file.elements.push(Element::draw_small_monospaced_text(
+            x,
+            y + scale + scale * 2 * row as i32,
+            locked,
+            line,
+        ));
+    }
+    (width, scale * (rows * 2 + 1))
+}
+
+/// Draws every drawable next to each other, each preceded by its vertically centred label
+///
+/// Returns the total width and the height of the tallest drawable
+fn draw_labelled(
+    file: &mut ExcalidrawFile,
+    x: i32,
+    y: i32,
+    locked: bool,
+    drawables: &[(&str, &dyn Drawable)],
+) -> (i32, i32) {
+    let scale = file.app_state.grid_size;
+    let mut width = 0;
+    let mut height = 0;
+    for (label, drawable) in drawables {
+        if width > 0 {
+            width += scale * 2;
+        }
+        let label_x = x + width;
+        width += label.chars().count() as i32 * 10 + scale;
+        let (drawable_width, drawable_height) = drawable.draw(file, x + width, y, locked);
+        file.elements.push(Element::draw_small_monospaced_text(
+            label_x,
+            y + drawable_height / 2 - scale / 2,
+            locked,
+            label.to_string(),
+        ));
+        width += drawable_width;
+        height = height.max(drawable_height);
+    }
+    (width, height)
+}
+
+/// Formats a single matrix entry, dropping trailing zeros
+fn format_entry(value: f64, precision: usize) -> String {
+    let res_string = format!("{:.1$}", value, precision);
+    let res = if res_string.contains('.') {
+        res_string.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        res_string.as_str()
+    };
+    if res == "-0" {
+        String::from("0")
+    } else {
+        res.to_string()
+    }
+}
+
+/// Returns the widest cell (in characters) of every column
+fn column_widths(cells: &[Vec<String>]) -> Vec<usize> {
+    let columns = cells.iter().map(Vec::len).max().unwrap_or(0);
+    (0..columns)
+        .map(|col| {
+            cells
+                .iter()
+                .filter_map(|row| row.get(col))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Writes a bracketed matrix with a vertical separator after the first `split` columns
+///
+/// If `split` covers every column no separator is written
+fn fmt_augmented(
+    f: &mut std::fmt::Formatter<'_>,
+    cells: &[Vec<String>],
+    split: usize,
+) -> std::fmt::Result {
+    fmt_augmented_with_header(f, &[], cells, split)
+}
+
+/// Writes a bracketed matrix like [`fmt_augmented`] with `header` above the first columns
+fn fmt_augmented_with_header(
+    f: &mut std::fmt::Formatter<'_>,
+    header: &[String],
+    cells: &[Vec<String>],
+    split: usize,
+) -> std::fmt::Result {
+    let widths = column_widths(&with_header(header, cells));
+    let split = split.min(widths.len());
+    let (col_widths, res_widths) = widths.split_at(split);
+    let res_len = if res_widths.is_empty() {
+        1
+    } else {
+        res_widths.iter().sum::<usize>() + res_widths.len() + 1
+    };
+    let width = col_widths.iter().sum::<usize>() + split + res_len - 2;
+    if !header.is_empty() {
+        f.write_str("\n ")?;
+        for (label, cw) in header.iter().zip(col_widths) {
+            f.write_fmt(format_args!(" {:>cw$}", label, cw = cw))?;
+        }
+    }
+    f.write_fmt(format_args!("\n┌╴{:w$}╶┐\n", "", w = width))?;
+    for row in cells {
+        f.write_str("│")?;
+        for (cell, cw) in row.iter().zip(col_widths) {
+            f.write_fmt(format_args!(" {:>cw$}", cell, cw = cw))?;
+        }
+        if res_widths.is_empty() {
+            f.write_str(" ")?;
+        } else {
+            f.write_str("│")?;
+            for (cell, rw) in row[split..].iter().zip(res_widths) {
+                f.write_fmt(format_args!("{:>rw$} ", cell, rw = rw))?;
+            }
+        }
+        f.write_str("│\n")?;
+    }
+    f.write_fmt(format_args!("└╴{:w$}╶┘", "", w = width))
+}
+
+/// The header followed by the cells, so the header counts for the column widths
+fn with_header(header: &[String], cells: &[Vec<String>]) -> Vec<Vec<String>> {
+    std::iter::once(header.to_vec())
+        .chain(cells.iter().cloned())
+        .collect()
+}
+
+/// Draws a bracketed matrix with a vertical separator after the first `split` columns
+///
+/// Returns the width and height of the drawn matrix
+fn draw_augmented(
+    file: &mut ExcalidrawFile,
+    x: i32,
+    y: i32,
+    locked: bool,
+    cells: &[Vec<String>],
+    split: usize,
+) -> (i32, i32) {
+    draw_augmented_with_header(file, x, y, locked, &[], cells, split)
+}
+
+/// Draws a bracketed matrix like [`draw_augmented`] with `header` above the first columns
+fn draw_augmented_with_header(
+    file: &mut ExcalidrawFile,
+    x: i32,
+    mut y: i32,
+    locked: bool,
+    header: &[String],
+    cells: &[Vec<String>],
+    split: usize,
+) -> (i32, i32) {
+    let scale = file.app_state.grid_size;
+    let header_height = if header.is_empty() { 0 } else { scale * 2 };
+    y += header_height;
+    let rows = cells.len() as i32;
+    let height = scale * (rows * 2 + 1);
+    let widths = column_widths(&with_header(header, cells));
+    let split = split.min(widths.len());
+    file.elements.push(Element::simple_line(
+        x,
+        y,
+        locked,
+        vec![[scale, 0], [0, 0], [0, height], [scale, height]],
+    ));
+    let mut current_offset = scale;
+    for (col, width) in widths.iter().enumerate() {
+        if col == split {
+            file.elements.push(Element::simple_line(
+                x + current_offset,
+                y + scale,
+                locked,
+                vec![[0, 0], [0, scale * (rows * 2 - 1)]],
+            ));
+            current_offset += scale;
+        }
+        if let Some(label) = header.get(col) {
+            file.elements.push(Element::draw_small_monospaced_text(
+                x + current_offset,
+                y - scale,
+                locked,
+                label.clone(),
+            ));
+        }
+        for (row, cells) in cells.iter().enumerate() {
+            file.elements.push(Element::draw_small_monospaced_text(
+                x + current_offset,
+                y + scale + scale * 2 * row as i32,
+                locked,
+                cells[col].clone(),
+            ));
+        }
+        current_offset += *width as i32 * 10 + scale;
+    }
+    current_offset -= scale;
+    file.elements.push(Element::simple_line(
+        x + current_offset,
+        y,
+        locked,
+        vec![[0, 0], [scale, 0], [scale, height], [0, height]],
+    ));
+    (current_offset + scale, height + header_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_entry_drops_trailing_zeros() {
+        assert_eq!(format_entry(1.5, 3), "1.5");
+        assert_eq!(format_entry(2.0, 3), "2");
+        assert_eq!(format_entry(-0.0001, 3), "0");
+    }
+
+    #[test]
+    fn format_entry_keeps_integers_without_decimals() {
+        assert_eq!(format_entry(100.0, 0), "100");
+        assert_eq!(format_entry(-20.0, 0), "-20");
+        assert_eq!(format_entry(0.4, 0), "0");
+    }
+
+    #[test]
+    fn swap_exchanges_two_lines() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]).swap(1, 2);
+        assert_eq!(matrix[1].coefficients(), &[3.0, 4.0]);
+        assert_eq!(matrix[1].result(), 6.0);
+        assert_eq!(matrix[2].coefficients(), &[1.0, 2.0]);
+        assert_eq!(matrix[2].result(), 5.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid other line")]
+    fn swap_panics_on_invalid_lines() {
+        LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]).swap(1, 3);
+    }
+
+    #[test]
+    fn fixed_size_matrices_swap_rows() {
+        let mut matrix = Matrix2x2::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]);
+        matrix.swap(0, 1);
+        assert_eq!(matrix.0, [[3.0, 4.0], [1.0, 2.0]]);
+        assert_eq!(matrix.1, [6.0, 5.0]);
+        let mut matrix = Matrix3x3::new(
+            [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]],
+            [1.0, 2.0, 3.0],
+        );
+        matrix.swap(0, 2);
+        assert_eq!(matrix.0[0], [7.0, 8.0, 9.0]);
+        assert_eq!(matrix.1, [3.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn fallible_operations_chain_like_the_panicking_ones() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]);
+        let chained = matrix
+            .try_mul(1, 2.0)
+            .and_then(|matrix| matrix.try_swap(1, 2))
+            .and_then(|matrix| matrix.try_mul_to(2, 1, -1.5))
+            .unwrap();
+        let expected = matrix.mul(1, 2.0).swap(1, 2).mul_to(2, 1, -1.5);
+        for line in 1..=2 {
+            assert_eq!(chained[line].coefficients(), expected[line].coefficients());
+            assert_eq!(chained[line].result(), expected[line].result());
+        }
+    }
+
+    #[test]
+    fn fallible_operations_report_what_is_wrong() {
+        use with_result::error::RowOperationError;
+        let matrix = LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]);
+        let cases = [
+            (matrix.try_mul(0, 2.0), RowOperationError::InvalidLine(0)),
+            (matrix.try_div(3, 2.0), RowOperationError::InvalidLine(3)),
+            (matrix.try_div(1, 0.0), RowOperationError::DivisionByZero),
+            (
+                matrix.try_div_to(1, 2, 0.0),
+                RowOperationError::DivisionByZero,
+            ),
+            (matrix.try_add_to(2, 2), RowOperationError::SameLine(2)),
+            (matrix.try_swap(1, 1), RowOperationError::SameLine(1)),
+            (
+                matrix.try_swap_columns(1, 3),
+                RowOperationError::InvalidColumn(3),
+            ),
+            (
+                matrix.try_mul(1, f64::INFINITY),
+                RowOperationError::NonFinite,
+            ),
+            (
+                matrix.try_mul_to(1, 2, f64::MAX),
+                RowOperationError::NonFinite,
+            ),
+        ];
+        for (result, error) in cases {
+            assert_eq!(result.err(), Some(error));
+        }
+    }
+
+    #[test]
+    fn get_rejects_line_zero() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]);
+        assert!(matrix.get(0).is_none());
+        assert!(matrix.get(3).is_none());
+        assert_eq!(matrix.get(2).map(|line| line.result()), Some(6.0));
+    }
+
+    #[test]
+    fn rectangular_matrices_place_the_separator_after_the_coefficients() {
+        let matrix =
+            with_result::AugmentedMatrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]], [7.0, 8.0]);
+        assert_eq!(
+            format!("{matrix}"),
+            "\n┌╴       ╶┐\n│ 1 2 3│7 │\n│ 4 5 6│8 │\n└╴       ╶┘"
+        );
+    }
+
+    #[test]
+    fn rectangular_matrices_support_the_row_operations() {
+        let matrix =
+            with_result::AugmentedMatrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]], [7.0, 8.0])
+                .mul_to(1, 2, -4.0)
+                .swap_columns(1, 3);
+        assert_eq!(matrix[2].coefficients(), &[-6.0, -3.0, 0.0]);
+        assert_eq!(matrix[2].result(), -20.0);
+        assert_eq!(
+            matrix.try_swap_columns(1, 4).err(),
+            Some(with_result::error::RowOperationError::InvalidColumn(4))
+        );
+        let transposed = matrix.transpose();
+        assert_eq!(transposed[3].coefficients(), &[1.0, 0.0]);
+    }
+
+    #[test]
+    fn several_result_columns_share_one_separator() {
+        let matrix = with_result::AugmentedMatrix::with_results(
+            [[1.0, 2.0], [3.0, 4.0]],
+            [[1.0, 0.0], [0.0, 1.0]],
+        );
+        assert_eq!(matrix[2].results(), &[0.0, 1.0]);
+        assert_eq!(
+            format!("{matrix}"),
+            "\n┌╴       ╶┐\n│ 1 2│1 0 │\n│ 3 4│0 1 │\n└╴       ╶┘"
+        );
+        let inverse = matrix.to_reduced_row_echelon().result();
+        assert_eq!(
+            format!("{inverse}"),
+            "\n┌╴            ╶┐\n│ 1 0│ -2    1 │\n│ 0 1│1.5 -0.5 │\n└╴            ╶┘"
+        );
+    }
+
+    #[test]
+    fn integer_matrices_are_exact_and_can_be_approximated() {
+        let matrix = with_result::AugmentedMatrix::from_integers([[1, 2], [3, 4]], [1, 3]);
+        let solution = matrix.to_reduced_row_echelon().result();
+        assert_eq!(solution[1].result(), rational::Rational::from(1));
+        assert_eq!(solution[2].result(), rational::Rational::from(0));
+        let approximated = matrix.div(1, rational::Rational::from(3)).map(f64::from);
+        assert_eq!(approximated[1].coefficients(), &[1.0 / 3.0, 2.0 / 3.0]);
+        assert_eq!(approximated[2].result(), 3.0);
+    }
+
+    #[test]
+    fn fixed_size_matrices_have_determinants() {
+        let matrix = Matrix2x2::new([[1.0, 2.0], [3.0, 4.0]], [0.0; 2]);
+        assert_eq!(matrix.determinant(), -2.0);
+        let matrix = Matrix3x3::new(
+            [[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [2.0, 0.0, 3.0]],
+            [0.0; 3],
+        );
+        assert!((matrix.determinant() - -8.0).abs() < 1e-12);
+        assert_eq!(matrix.determinant_expansion().value, -8.0);
+    }
+
+    #[test]
+    fn div_accepts_small_values() {
+        let matrix = LineMatrix::new([[1e-11, 0.0], [0.0, 1.0]], [2e-11, 1.0]);
+        let divided = matrix.div(1, 1e-11);
+        assert_eq!(divided[1].coefficients(), &[1.0, 0.0]);
+        assert_eq!(divided[1].result(), 2.0);
+        assert!(matrix.try_div(1, 1e-11).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by zero")]
+    fn div_rejects_zero() {
+        LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]).div(1, 0.0);
+    }
+}
@@ -0,0 +1,260 @@
+use super::format_entry;
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// A complex number `re + im·i`
+#[derive(Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const ZERO: Self = Self { re: 0.0, im: 0.0 };
+    pub const ONE: Self = Self { re: 1.0, im: 0.0 };
+    pub const I: Self = Self { re: 0.0, im: 1.0 };
+
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// The absolute value `|z|`
+    pub fn norm(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Formats the number with at most `precision` decimals per part, like `1.5-2i`
+    pub fn format(self, precision: usize) -> String {
+        let re = format_entry(self.re, precision);
+        let im = format_entry(self.im.abs(), precision);
+        if im == "0" {
+            return re;
+        }
+        let im = if im == "1" {
+            String::from("i")
+        } else {
+            im + "i"
+        };
+        let sign = if self.im < 0.0 { "-" } else { "+" };
+        if re == "0" {
+            format!("{}{im}", sign.trim_start_matches('+'))
+        } else {
+            format!("{re}{sign}{im}")
+        }
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let denominator = rhs.re * rhs.re + rhs.im * rhs.im;
+        let numerator = self * rhs.conj();
+        Self::new(numerator.re / denominator, numerator.im / denominator)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl AddAssign for Complex {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Complex {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Complex {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Complex {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Debug for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format(f.precision().unwrap_or(3)))
+    }
+}
+
+/// The text is not a complex number like `1.5-2i`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseComplexError(pub String);
+
+impl Display for ParseComplexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("Invalid number `{}`", self.0))
+    }
+}
+
+impl Error for ParseComplexError {}
+
+impl FromStr for Complex {
+    type Err = ParseComplexError;
+
+    /// Parses the forms written by [`Complex::format`], like `2`, `-i`, `3i` or `1.5-2i`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseComplexError(s.to_string());
+        let s = s.trim();
+        let Some(rest) = s.strip_suffix('i') else {
+            return s.parse::<f64>().map(Self::from).map_err(|_| error());
+        };
+        // The imaginary part starts at the last sign that is not the first character, signs of
+        // exponents like in `2e-3i` are skipped
+        let split = rest
+            .char_indices()
+            .skip(1)
+            .filter(|&(i, c)| (c == '+' || c == '-') && !rest[..i].ends_with(['e', 'E']))
+            .map(|(i, _)| i)
+            .last()
+            .unwrap_or(0);
+        let (re, im) = rest.split_at(split);
+        let re = if re.is_empty() {
+            0.0
+        } else {
+            re.parse().map_err(|_| error())?
+        };
+        let im = match im {
+            "" | "+" => 1.0,
+            "-" => -1.0,
+            im => im.parse().map_err(|_| error())?,
+        };
+        Ok(Self::new(re, im))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let z = Complex::new(1.0, 2.0);
+        let w = Complex::new(3.0, -1.0);
+        assert_eq!(z + w, Complex::new(4.0, 1.0));
+        assert_eq!(z - w, Complex::new(-2.0, 3.0));
+        assert_eq!(z * w, Complex::new(5.0, 5.0));
+        assert_eq!(z * w / w, z);
+        assert_eq!(Complex::I * Complex::I, -Complex::ONE);
+        assert_eq!(Complex::new(3.0, 4.0).norm(), 5.0);
+    }
+
+    #[test]
+    fn formats_like_by_hand() {
+        let cases = [
+            (Complex::new(2.0, 0.0), "2"),
+            (Complex::new(0.0, 1.0), "i"),
+            (Complex::new(0.0, -1.0), "-i"),
+            (Complex::new(0.0, 3.0), "3i"),
+            (Complex::new(1.5, -2.0), "1.5-2i"),
+            (Complex::new(-1.0, 1.0), "-1+i"),
+            (Complex::new(1.0, 1e-5), "1"),
+        ];
+        for (value, text) in cases {
+            assert_eq!(value.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn parses_the_formatted_forms() {
+        let cases = [
+            ("2", Complex::new(2.0, 0.0)),
+            ("i", Complex::I),
+            ("-i", -Complex::I),
+            ("+i", Complex::I),
+            ("3i", Complex::new(0.0, 3.0)),
+            ("1.5-2i", Complex::new(1.5, -2.0)),
+            ("-1+i", Complex::new(-1.0, 1.0)),
+            (" 4 ", Complex::new(4.0, 0.0)),
+        ];
+        for (text, value) in cases {
+            assert_eq!(text.parse(), Ok(value), "{text}");
+        }
+    }
+
+    #[test]
+    fn parses_exponents() {
+        let cases = [
+            ("2e-3i", Complex::new(0.0, 2e-3)),
+            ("1e5", Complex::new(1e5, 0.0)),
+            ("1E+2-3e-1i", Complex::new(100.0, -0.3)),
+            ("-1e-3+2E3i", Complex::new(-1e-3, 2e3)),
+        ];
+        for (text, value) in cases {
+            assert_eq!(text.parse(), Ok(value), "{text}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_numbers() {
+        for text in ["", "1+", "ii", "1+2j", "e-3i"] {
+            assert_eq!(
+                text.parse::<Complex>(),
+                Err(ParseComplexError(text.to_string())),
+                "{text}"
+            );
+        }
+    }
+}
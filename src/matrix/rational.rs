@@ -0,0 +1,356 @@
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// An exact fraction
+///
+/// Always stored in lowest terms with a positive denominator, so `2/4` and `1/2` are equal
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i64,
+    denominator: i64,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self {
+        numerator: 0,
+        denominator: 1,
+    };
+    pub const ONE: Self = Self {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    /// Panics if `denominator` is zero
+    pub fn new(numerator: i64, denominator: i64) -> Self {
+        Self::reduce(numerator as i128, denominator as i128)
+    }
+
+    pub fn numerator(self) -> i64 {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> i64 {
+        self.denominator
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.denominator == 1
+    }
+
+    /// Panics if the numerator is `i64::MIN`, whose absolute value does not fit into `i64`
+    pub fn abs(self) -> Self {
+        Self {
+            numerator: self.numerator.checked_abs().expect("Rational overflow"),
+            denominator: self.denominator,
+        }
+    }
+
+    /// Panics if the value is zero
+    pub fn recip(self) -> Self {
+        Self::new(self.denominator, self.numerator)
+    }
+
+    /// Brings the fraction into lowest terms, panicking if it does not fit into `i64`
+    fn reduce(mut numerator: i128, mut denominator: i128) -> Self {
+        if denominator == 0 {
+            panic!("Division by zero");
+        }
+        if denominator < 0 {
+            numerator = -numerator;
+            denominator = -denominator;
+        }
+        let gcd = gcd(numerator.abs(), denominator);
+        Self {
+            numerator: (numerator / gcd).try_into().expect("Rational overflow"),
+            denominator: (denominator / gcd).try_into().expect("Rational overflow"),
+        }
+    }
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+}
+
+impl From<i32> for Rational {
+    fn from(value: i32) -> Self {
+        Self::from(value as i64)
+    }
+}
+
+impl From<Rational> for f64 {
+    fn from(value: Rational) -> Self {
+        value.numerator as f64 / value.denominator as f64
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator as i128 * other.denominator as i128)
+            .cmp(&(other.numerator as i128 * self.denominator as i128))
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::reduce(
+            self.numerator as i128 * rhs.denominator as i128
+                + rhs.numerator as i128 * self.denominator as i128,
+            self.denominator as i128 * rhs.denominator as i128,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::reduce(
+            self.numerator as i128 * rhs.denominator as i128
+                - rhs.numerator as i128 * self.denominator as i128,
+            self.denominator as i128 * rhs.denominator as i128,
+        )
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::reduce(
+            self.numerator as i128 * rhs.numerator as i128,
+            self.denominator as i128 * rhs.denominator as i128,
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::reduce(
+            self.numerator as i128 * rhs.denominator as i128,
+            self.denominator as i128 * rhs.numerator as i128,
+        )
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            numerator: self.numerator.checked_neg().expect("Rational overflow"),
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl AddAssign for Rational {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Rational {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Rational {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Rational {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Debug for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            f.write_fmt(format_args!("{}", self.numerator))
+        } else {
+            f.write_fmt(format_args!("{}/{}", self.numerator, self.denominator))
+        }
+    }
+}
+
+/// The text is neither an integer, a fraction like `-1/3` nor a decimal like `0.25`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseRationalError(pub String);
+
+impl Display for ParseRationalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("Invalid number `{}`", self.0))
+    }
+}
+
+impl Error for ParseRationalError {}
+
+impl FromStr for Rational {
+    type Err = ParseRationalError;
+
+    /// Parses integers, fractions like `-1/3` and decimals like `0.25` exactly
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseRationalError(s.to_string());
+        let s = s.trim();
+        if let Some((numerator, denominator)) = s.split_once('/') {
+            if denominator.contains('/') {
+                return Err(error());
+            }
+            let numerator = numerator.trim().parse::<Self>().map_err(|_| error())?;
+            let denominator = denominator.trim().parse::<Self>().map_err(|_| error())?;
+            if denominator == Self::ZERO {
+                return Err(error());
+            }
+            return Ok(numerator / denominator);
+        }
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer.is_empty() && fraction.is_empty()
+            || !integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(error());
+        }
+        let mut numerator: i128 = 0;
+        let mut denominator: i128 = 1;
+        for digit in integer.chars().chain(fraction.chars()) {
+            numerator = numerator
+                .checked_mul(10)
+                .and_then(|n| n.checked_add(digit.to_digit(10).unwrap() as i128))
+                .ok_or_else(error)?;
+        }
+        for _ in fraction.chars() {
+            denominator = denominator.checked_mul(10).ok_or_else(error)?;
+        }
+        if negative {
+            numerator = -numerator;
+        }
+        let gcd = gcd(numerator.abs(), denominator);
+        Ok(Self {
+            numerator: (numerator / gcd).try_into().map_err(|_| error())?,
+            denominator: (denominator / gcd).try_into().map_err(|_| error())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fractions_are_kept_in_lowest_terms() {
+        let value = Rational::new(2, -4);
+        assert_eq!((value.numerator(), value.denominator()), (-1, 2));
+        assert_eq!(Rational::new(6, 3), Rational::from(2));
+        assert!(Rational::new(6, 3).is_integer());
+    }
+
+    #[test]
+    fn arithmetic_is_exact() {
+        let third = Rational::new(1, 3);
+        assert_eq!(third + third + third, Rational::ONE);
+        assert_eq!(third - Rational::new(1, 2), Rational::new(-1, 6));
+        assert_eq!(third * Rational::from(6), Rational::from(2));
+        assert_eq!(third / Rational::new(2, 3), Rational::new(1, 2));
+        assert_eq!(third.recip(), Rational::from(3));
+        assert!(Rational::new(-1, 2) < third);
+    }
+
+    #[test]
+    fn parses_integers_fractions_and_decimals() {
+        let cases = [
+            ("3", Rational::from(3)),
+            ("-1/3", Rational::new(-1, 3)),
+            (" 4 / 6 ", Rational::new(2, 3)),
+            ("0.25", Rational::new(1, 4)),
+            ("-.5", Rational::new(-1, 2)),
+            ("+2.", Rational::from(2)),
+        ];
+        for (text, value) in cases {
+            assert_eq!(text.parse::<Rational>(), Ok(value), "{text}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_numbers() {
+        for text in ["", ".", "1/0", "1/2/3", "1//2", "a", "1e3", "--1"] {
+            assert_eq!(
+                text.parse::<Rational>(),
+                Err(ParseRationalError(text.to_string())),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn displays_integers_without_a_denominator() {
+        assert_eq!(Rational::new(-3, 6).to_string(), "-1/2");
+        assert_eq!(Rational::new(4, 2).to_string(), "2");
+    }
+
+    #[test]
+    #[should_panic(expected = "Rational overflow")]
+    fn negating_the_smallest_integer_overflows() {
+        let _ = -Rational::from(i64::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "Rational overflow")]
+    fn the_absolute_value_of_the_smallest_integer_overflows() {
+        Rational::from(i64::MIN).abs();
+    }
+
+    #[test]
+    fn subtracting_the_smallest_integer_from_itself_is_zero() {
+        let min = Rational::from(i64::MIN);
+        assert_eq!(min - min, Rational::ZERO);
+    }
+}
@@ -0,0 +1,237 @@
+use super::{complex::Complex, format_entry, rational::Rational};
+use std::{
+    fmt::Debug,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// Floating point values with an absolute value below this are treated as zero
+pub const EPSILON: f64 = 1e-10;
+
+/// The counterpart of [`EPSILON`] for the lower precision of `f32`
+pub const EPSILON_F32: f32 = 1e-5;
+
+/// A number the matrices can be built from
+pub trait Scalar:
+    Copy
+    + PartialEq
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    /// Whether the value is zero, inexact types compare against [`EPSILON`]
+    fn is_zero(self) -> bool;
+
+    /// Whether the value is zero next to the other values of a matrix, inexact types treat
+    /// magnitudes below `tolerance` as zero while exact types only compare against zero
+    fn is_negligible(self, tolerance: f64) -> bool;
+
+    fn is_finite(self) -> bool;
+
+    fn is_negative(self) -> bool;
+
+    /// The absolute value, used to compare pivots
+    fn magnitude(self) -> f64;
+
+    /// Formats the value for a matrix cell
+    ///
+    /// `precision` is the maximum number of decimals and is ignored by exact types
+    fn format(self, precision: usize) -> String;
+}
+
+impl Scalar for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn one() -> Self {
+        1.0
+    }
+
+    fn is_zero(self) -> bool {
+        self.abs() < EPSILON
+    }
+
+    fn is_negligible(self, tolerance: f64) -> bool {
+        self == 0.0 || self.abs() < tolerance
+    }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+
+    fn is_negative(self) -> bool {
+        self < 0.0
+    }
+
+    fn magnitude(self) -> f64 {
+        self.abs()
+    }
+
+    fn format(self, precision: usize) -> String {
+        format_entry(self, precision)
+    }
+}
+
+impl Scalar for f32 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn one() -> Self {
+        1.0
+    }
+
+    fn is_zero(self) -> bool {
+        self.abs() < EPSILON_F32
+    }
+
+    fn is_negligible(self, tolerance: f64) -> bool {
+        self == 0.0 || (self.abs() as f64) < tolerance
+    }
+
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+
+    fn is_negative(self) -> bool {
+        self < 0.0
+    }
+
+    fn magnitude(self) -> f64 {
+        self.abs() as f64
+    }
+
+    fn format(self, precision: usize) -> String {
+        format_entry(self as f64, precision)
+    }
+}
+
+/// Integers are represented as rationals, so they stay exact under division
+impl Scalar for Rational {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn is_zero(self) -> bool {
+        self.numerator() == 0
+    }
+
+    fn is_negligible(self, _tolerance: f64) -> bool {
+        self.is_zero()
+    }
+
+    fn is_finite(self) -> bool {
+        true
+    }
+
+    fn is_negative(self) -> bool {
+        self.numerator() < 0
+    }
+
+    fn magnitude(self) -> f64 {
+        f64::from(self.abs())
+    }
+
+    fn format(self, _precision: usize) -> String {
+        self.to_string()
+    }
+}
+
+impl Scalar for Complex {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn is_zero(self) -> bool {
+        self.re.is_zero() && self.im.is_zero()
+    }
+
+    fn is_negligible(self, tolerance: f64) -> bool {
+        self == Self::ZERO || self.norm() < tolerance
+    }
+
+    fn is_finite(self) -> bool {
+        self.re.is_finite() && self.im.is_finite()
+    }
+
+    /// Only real numbers can be negative
+    fn is_negative(self) -> bool {
+        self.im == 0.0 && self.re < 0.0
+    }
+
+    fn magnitude(self) -> f64 {
+        self.norm()
+    }
+
+    fn format(self, precision: usize) -> String {
+        Complex::format(self, precision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inexact_types_compare_against_their_epsilon() {
+        assert!(Scalar::is_zero(1e-11_f64));
+        assert!(!Scalar::is_zero(1e-9_f64));
+        assert!(Scalar::is_zero(1e-6_f32));
+        assert!(!Scalar::is_zero(1e-4_f32));
+        assert!(Complex::new(1e-11, -1e-11).is_zero());
+        assert!(!Complex::new(0.0, 1e-9).is_zero());
+        assert!(Rational::ZERO.is_zero());
+    }
+
+    #[test]
+    fn only_real_complex_numbers_are_negative() {
+        assert!(Complex::new(-1.0, 0.0).is_negative());
+        assert!(!Complex::new(-1.0, 1.0).is_negative());
+        assert!(Rational::new(-1, 2).is_negative());
+    }
+
+    #[test]
+    fn magnitude_is_the_absolute_value() {
+        assert_eq!((-2.5_f64).magnitude(), 2.5);
+        assert_eq!((-2.5_f32).magnitude(), 2.5);
+        assert_eq!(Rational::new(-1, 4).magnitude(), 0.25);
+        assert_eq!(Complex::new(3.0, -4.0).magnitude(), 5.0);
+    }
+
+    #[test]
+    fn formats_exact_types_without_rounding() {
+        assert_eq!(Rational::new(1, 3).format(2), "1/3");
+        assert_eq!((1.0_f64 / 3.0).format(2), "0.33");
+        assert_eq!(0.5_f32.format(3), "0.5");
+    }
+
+    #[test]
+    fn only_inexact_types_neglect_small_values() {
+        assert!(1e-9_f64.is_negligible(1e-8));
+        assert!(!1e-9_f64.is_negligible(1e-10));
+        assert!(0.0_f64.is_negligible(0.0));
+        assert!(1e-9_f32.is_negligible(1e-8));
+        assert!(Complex::new(0.0, 1e-9).is_negligible(1e-8));
+        assert!(!Rational::new(1, 1_000_000_000).is_negligible(1e-8));
+        assert!(Rational::ZERO.is_negligible(0.0));
+    }
+}
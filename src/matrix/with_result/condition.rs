@@ -0,0 +1,154 @@
+use super::{elimination::roman, error::SingularMatrix, AugmentedMatrix};
+use crate::matrix::scalar::Scalar;
+use std::fmt::{Debug, Display};
+
+/// Pivots smaller than this times the largest coefficient are reported by the elimination,
+/// dividing by them amplifies rounding errors
+pub const PIVOT_TOLERANCE: f64 = 1e-8;
+
+/// A matrix norm induced by a vector norm
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Norm {
+    /// The largest sum of magnitudes of a column
+    One,
+    /// The largest sum of magnitudes of a line
+    Infinity,
+}
+
+impl Display for Norm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::One => "1",
+            Self::Infinity => "∞",
+        })
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    AugmentedMatrix<ROWS, COLS, RESULTS, T>
+{
+    /// The norm of the coefficients, the results are ignored
+    pub fn norm(&self, norm: Norm) -> f64 {
+        let magnitude = |line: usize, col: usize| self.0[line].0[col].magnitude();
+        match norm {
+            Norm::One => (0..COLS)
+                .map(|col| (0..ROWS).map(|line| magnitude(line, col)).sum::<f64>())
+                .fold(0.0, f64::max),
+            Norm::Infinity => (0..ROWS)
+                .map(|line| (0..COLS).map(|col| magnitude(line, col)).sum::<f64>())
+                .fold(0.0, f64::max),
+        }
+    }
+}
+
+impl<const SIZE: usize, const RESULTS: usize, T: Scalar> AugmentedMatrix<SIZE, SIZE, RESULTS, T> {
+    /// The condition number `κ(A) = |A|·|A⁻¹|` of the coefficients
+    ///
+    /// It is at least one, large values mean that small changes of the results can change the
+    /// solution a lot
+    pub fn condition_number(&self, norm: Norm) -> Result<f64, SingularMatrix> {
+        Ok(self.norm(norm) * self.inverse()?.norm(norm))
+    }
+}
+
+/// A pivot candidate whose magnitude is below [`PIVOT_TOLERANCE`] times the largest coefficient
+#[derive(Clone, Copy, PartialEq)]
+pub struct SmallPivot {
+    /// **1-based**
+    pub line: usize,
+    /// **1-based**
+    pub column: usize,
+    pub magnitude: f64,
+    /// The bound it is below
+    pub tolerance: f64,
+}
+
+impl Debug for SmallPivot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl Display for SmallPivot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "Warning: pivot of line {} in column {} is close to zero ({:.1e} < {:.1e})",
+            roman(self.line),
+            self.column,
+            self.magnitude,
+            self.tolerance
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::with_result::{solution::SolutionSet, LineMatrix};
+
+    #[test]
+    fn norms_sum_columns_or_lines() {
+        let matrix = LineMatrix::new([[1.0, -2.0], [-3.0, 4.0]], [100.0; 2]);
+        assert_eq!(matrix.norm(Norm::One), 6.0);
+        assert_eq!(matrix.norm(Norm::Infinity), 7.0);
+    }
+
+    #[test]
+    fn condition_number_multiplies_the_norms_of_a_and_its_inverse() {
+        let matrix = LineMatrix::new([[2.0, 0.0], [0.0, 0.5]], [0.0; 2]);
+        assert_eq!(matrix.condition_number(Norm::One), Ok(4.0));
+        let singular = LineMatrix::new([[1.0, 2.0], [2.0, 4.0]], [0.0; 2]);
+        assert!(singular.condition_number(Norm::Infinity).is_err());
+    }
+
+    #[test]
+    fn pivots_close_to_zero_are_reported() {
+        let elimination = LineMatrix::new([[1e-17, 1.0], [1.0, 1.0]], [1.0, 2.0]).to_row_echelon();
+        let [warning] = elimination.warnings.as_slice() else {
+            panic!("expected one warning, got {:?}", elimination.warnings);
+        };
+        assert_eq!((warning.line, warning.column), (1, 1));
+        assert_eq!(warning.magnitude, 1e-17);
+        assert_eq!(
+            warning.to_string(),
+            "Warning: pivot of line I in column 1 is close to zero (1.0e-17 < 1.0e-8)"
+        );
+        let elimination = LineMatrix::new([[1e-9, 1.0], [1.0, 1.0]], [1.0, 2.0]).to_row_echelon();
+        assert_eq!(elimination.warnings.len(), 1);
+    }
+
+    #[test]
+    fn the_tolerance_is_relative_to_the_largest_coefficient() {
+        let small = LineMatrix::new([[1e-9, 0.0], [0.0, 1e-9]], [1.0, 2.0]).to_row_echelon();
+        assert!(small.warnings.is_empty());
+        let large = LineMatrix::new([[1.0, 1e9], [1e9, 1.0]], [1.0, 2.0]).to_row_echelon();
+        let [warning] = large.warnings.as_slice() else {
+            panic!("expected one warning, got {:?}", large.warnings);
+        };
+        assert_eq!(warning.tolerance, 10.0);
+    }
+
+    #[test]
+    fn well_conditioned_matrices_have_no_warnings() {
+        let elimination = LineMatrix::new([[2.0, 1.0], [4.0, 3.0]], [3.0, 7.0]).to_row_echelon();
+        assert!(elimination.warnings.is_empty());
+    }
+
+    #[test]
+    fn small_matrices_are_not_mistaken_for_zero() {
+        let matrix = LineMatrix::new([[1e-11, 0.0], [0.0, 1e-11]], [1e-11, 2e-11]);
+        assert_eq!(matrix.rank(), 2);
+        assert!(matrix.inverse().is_ok());
+        let solution = matrix.solve().unwrap();
+        assert_eq!(solution.values(), &[1.0, 2.0]);
+        assert!(matches!(matrix.solution_set(), SolutionSet::Unique(_)));
+        assert!(matrix.to_reduced_row_echelon().warnings.is_empty());
+    }
+
+    #[test]
+    fn pivots_small_next_to_the_other_entries_count_as_zero() {
+        let matrix = LineMatrix::new([[1.0, 1.0], [1.0, 1.0 + 1e-12]], [2.0, 2.0]);
+        assert_eq!(matrix.rank(), 1);
+        assert!(matrix.inverse().is_err());
+    }
+}
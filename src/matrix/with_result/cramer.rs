@@ -0,0 +1,237 @@
+use super::{
+    solution::{subscript, Solution},
+    variable::Variable,
+    AugmentedMatrix, Line, LineMatrix,
+};
+use crate::{
+    exporters::excalidraw::{Drawable, Element, ExcalidrawFile},
+    matrix::{draw_labelled, scalar::Scalar},
+};
+use std::fmt::{Debug, Display};
+
+/// A square matrix together with its determinant
+#[derive(Clone, Copy)]
+pub struct Determinant<const SIZE: usize, T = f64> {
+    pub matrix: AugmentedMatrix<SIZE, SIZE, 0, T>,
+    pub value: T,
+}
+
+/// The determinants needed to solve a system with Cramer's rule
+///
+/// `xᵢ = det(Aᵢ) / det(A)` where `Aᵢ` is `A` with column `i` replaced by the result column
+#[derive(Clone)]
+pub struct Cramer<const SIZE: usize, T = f64> {
+    pub coefficients: Determinant<SIZE, T>,
+    /// `A₁` to `Aₙ`
+    pub replaced: [Determinant<SIZE, T>; SIZE],
+}
+
+impl<const SIZE: usize, T: Scalar> LineMatrix<SIZE, T> {
+    /// Calculates every determinant of Cramer's rule
+    ///
+    /// Every matrix keeps the labels of the columns, the replaced column still belongs to the
+    /// same variable
+    pub fn cramer(&self) -> Cramer<SIZE, T> {
+        let determinant = |matrix: AugmentedMatrix<SIZE, SIZE, 0, T>| Determinant {
+            matrix,
+            value: matrix.determinant(),
+        };
+        Cramer {
+            coefficients: determinant(AugmentedMatrix(self.0.map(|line| Line(line.0, [])), self.1)),
+            replaced: std::array::from_fn(|col| {
+                determinant(AugmentedMatrix(
+                    self.0.map(|line| {
+                        let mut coefficients = line.0;
+                        coefficients[col] = line.result();
+                        Line(coefficients, [])
+                    }),
+                    self.1,
+                ))
+            }),
+        }
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Cramer<SIZE, T> {
+    /// Divides the determinants, `None` if `det(A)` is zero and the rule is not applicable
+    ///
+    /// The solution keeps the labels of the matrix
+    pub fn solution(&self) -> Option<Solution<SIZE, T>> {
+        let determinant = self.coefficients.value;
+        if determinant.is_zero() {
+            return None;
+        }
+        let solution = Solution::new(self.replaced.map(|replaced| replaced.value / determinant));
+        Some(match self.coefficients.matrix.labels() {
+            Some(&labels) => solution.with_labels(labels),
+            None => solution,
+        })
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Debug for Cramer<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Display for Cramer<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        let names = names::<SIZE>();
+        let determinants = [&self.coefficients].into_iter().chain(&self.replaced);
+        for (name, determinant) in names.iter().zip(determinants) {
+            f.write_fmt(format_args!("\n{name} ="))?;
+            Display::fmt(&determinant.matrix, f)?;
+            f.write_fmt(format_args!(
+                "\ndet({name}) = {}\n",
+                determinant.value.format(precision)
+            ))?;
+        }
+        if self.solution().is_none() {
+            return f.write_str("\ndet(A) = 0, Cramer's rule is not applicable");
+        }
+        let divisor = self.coefficients.value.format(precision);
+        let divisor = if divisor.contains(['+', '-']) {
+            format!("({divisor})")
+        } else {
+            divisor
+        };
+        let labels = self
+            .coefficients
+            .matrix
+            .labels()
+            .copied()
+            .unwrap_or_else(Variable::defaults);
+        for (i, (replaced, label)) in self.replaced.iter().zip(labels).enumerate() {
+            f.write_fmt(format_args!(
+                "\n{label} = det(A{i}) / det(A) = {} / {} = {}",
+                replaced.value.format(precision),
+                divisor,
+                (replaced.value / self.coefficients.value).format(precision),
+                i = subscript(i + 1),
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Drawable for Cramer<SIZE, T> {
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        let scale = file.app_state.grid_size;
+        let names = names::<SIZE>();
+        let determinants = [&self.coefficients].into_iter().chain(&self.replaced);
+        let mut width = 0;
+        let mut height = 0;
+        for (name, determinant) in names.iter().zip(determinants) {
+            if width > 0 {
+                width += scale * 2;
+            }
+            let label = format!("{name} =");
+            let (matrix_width, matrix_height) =
+                draw_labelled(file, x + width, y, locked, &[(&label, &determinant.matrix)]);
+            let text = format!("det({name}) = {}", determinant.value.format(3));
+            let text_width = text.chars().count() as i32 * 10;
+            file.elements.push(Element::draw_small_monospaced_text(
+                x + width,
+                y + matrix_height + scale,
+                locked,
+                text,
+            ));
+            width += matrix_width.max(text_width);
+            height = height.max(matrix_height + scale * 2);
+        }
+        (width, height)
+    }
+}
+
+/// `A` followed by `A₁` to `Aₙ`
+fn names<const SIZE: usize>() -> Vec<String> {
+    [String::from("A")]
+        .into_iter()
+        .chain((1..=SIZE).map(|i| format!("A{}", subscript(i))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::rational::Rational;
+
+    #[test]
+    fn replaces_one_column_per_variable() {
+        let cramer = LineMatrix::from_integers([[2, 1], [1, 3]], [3, 5]).cramer();
+        assert_eq!(cramer.coefficients.value, Rational::from(5));
+        assert_eq!(
+            cramer.replaced[0].matrix[1].coefficients(),
+            &[3, 1].map(Rational::from)
+        );
+        assert_eq!(
+            cramer.replaced[1].matrix[2].coefficients(),
+            &[1, 5].map(Rational::from)
+        );
+        assert_eq!(
+            cramer.solution().unwrap().values(),
+            &[Rational::new(4, 5), Rational::new(7, 5)]
+        );
+    }
+
+    #[test]
+    fn every_matrix_keeps_the_labels() {
+        let labels = [Variable::new('a'), Variable::new('b')];
+        let cramer = LineMatrix::new([[2.0, 1.0], [1.0, 3.0]], [3.0, 5.0])
+            .with_labels(labels)
+            .cramer();
+        assert_eq!(cramer.coefficients.matrix.labels(), Some(&labels));
+        for replaced in &cramer.replaced {
+            assert_eq!(replaced.matrix.labels(), Some(&labels));
+        }
+    }
+
+    #[test]
+    fn is_not_applicable_to_singular_matrices() {
+        let cramer = LineMatrix::new([[1.0, 2.0], [2.0, 4.0]], [3.0, 6.0]).cramer();
+        assert!(cramer.solution().is_none());
+        assert!(cramer
+            .to_string()
+            .ends_with("det(A) = 0, Cramer's rule is not applicable"));
+    }
+
+    #[test]
+    fn writes_every_quotient() {
+        let text = LineMatrix::new([[2.0, 1.0], [1.0, 3.0]], [3.0, 5.0])
+            .cramer()
+            .to_string();
+        assert!(text.contains("\nx = det(A₁) / det(A) = 4 / 5 = 0.8"));
+        assert!(text.contains("\ny = det(A₂) / det(A) = 7 / 5 = 1.4"));
+    }
+
+    #[test]
+    fn quotients_are_named_by_the_labels() {
+        let labels = [Variable::indexed('a', 1), Variable::new('b')];
+        let cramer = LineMatrix::new([[2.0, 1.0], [1.0, 3.0]], [3.0, 5.0])
+            .with_labels(labels)
+            .cramer();
+        let text = cramer.to_string();
+        assert!(text.contains("\na₁ = det(A₁) / det(A) = 4 / 5 = 0.8"));
+        assert!(text.contains("\nb = det(A₂) / det(A) = 7 / 5 = 1.4"));
+        assert_eq!(cramer.solution().unwrap().labels(), Some(&labels));
+    }
+
+    #[test]
+    fn larger_systems_fall_back_to_indexed_names() {
+        let text = LineMatrix::new(
+            [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 2.0, 0.0, 0.0],
+                [0.0, 0.0, 4.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+            [1.0, 1.0, 1.0, 1.0],
+        )
+        .cramer()
+        .to_string();
+        assert!(text.contains("\nx₃ = det(A₃) / det(A) = 2 / 8 = 0.25"));
+        assert!(text.contains("\nx₄ = det(A₄) / det(A) = 8 / 8 = 1"));
+    }
+}
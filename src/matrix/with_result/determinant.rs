@@ -0,0 +1,370 @@
+use super::{
+    elimination::{Elimination, RowOperation},
+    solution::subscript,
+    AugmentedMatrix,
+};
+use crate::matrix::{fmt_augmented, scalar::Scalar};
+use std::fmt::{Debug, Display};
+
+impl<const SIZE: usize, const RESULTS: usize, T: Scalar> Elimination<SIZE, SIZE, RESULTS, T> {
+    /// The determinant of the coefficients of the starting matrix
+    ///
+    /// The result has to be triangular, like after [`AugmentedMatrix::to_row_echelon`]. The
+    /// product of its diagonal is corrected for the sign flip of every line or column swap and
+    /// the factor of every scaling
+    pub fn determinant(&self) -> T {
+        let result = self.result();
+        let mut determinant = (1..=SIZE).fold(T::one(), |acc, i| acc * result[i].0[i - 1]);
+        for operation in self.operations() {
+            match operation {
+                RowOperation::Swap { .. } | RowOperation::SwapColumns { .. } => {
+                    determinant = -determinant
+                }
+                RowOperation::Mul { value, .. } => determinant /= value,
+                RowOperation::Div { value, .. } => determinant *= value,
+                _ => {}
+            }
+        }
+        determinant
+    }
+}
+
+impl<const SIZE: usize, const RESULTS: usize, T: Scalar> AugmentedMatrix<SIZE, SIZE, RESULTS, T> {
+    /// Calculates the determinant of the coefficients using Gaussian elimination
+    pub fn determinant(&self) -> T {
+        self.to_row_echelon().determinant()
+    }
+
+    /// Calculates the determinant of the coefficients the way it is done by hand
+    ///
+    /// See [`DeterminantExpansion`]
+    pub fn determinant_expansion(&self) -> DeterminantExpansion<T> {
+        DeterminantExpansion::new(self.0.iter().map(|line| line.0.to_vec()).collect())
+    }
+}
+
+/// The calculation of a determinant by hand
+///
+/// 2×2 matrices use `a·d - b·c`, 3×3 matrices the rule of Sarrus and larger matrices are
+/// expanded along their first line
+#[derive(Clone, PartialEq)]
+pub struct DeterminantExpansion<T = f64> {
+    /// The square matrix, line by line
+    pub matrix: Vec<Vec<T>>,
+    pub method: ExpansionMethod<T>,
+    pub value: T,
+}
+
+/// The rule used to calculate a [`DeterminantExpansion`]
+#[derive(Clone, PartialEq)]
+pub enum ExpansionMethod<T = f64> {
+    /// The matrix has at most one entry
+    Single,
+    /// `a·d - b·c`
+    Cross,
+    /// The sum of the three diagonals minus the three anti-diagonals
+    Sarrus,
+    /// Laplace expansion along the first line, skipping zero entries
+    Laplace(Vec<Minor<T>>),
+}
+
+/// A term `factor · det(M₁ⱼ)` of a Laplace expansion
+#[derive(Clone, PartialEq)]
+pub struct Minor<T = f64> {
+    /// The removed column `j`, **1-based**
+    pub column: usize,
+    /// The entry of the first line including the sign `(-1)^(1+j)`
+    pub factor: T,
+    /// The matrix without the first line and column `j`
+    pub expansion: DeterminantExpansion<T>,
+}
+
+impl<T: Scalar> DeterminantExpansion<T> {
+    /// Expands a square matrix given line by line
+    ///
+    /// Panics if the matrix is not square
+    pub fn new(matrix: Vec<Vec<T>>) -> Self {
+        let size = matrix.len();
+        if matrix.iter().any(|line| line.len() != size) {
+            panic!("Invalid matrix size");
+        }
+        let (method, value) = match size {
+            0 => (ExpansionMethod::Single, T::one()),
+            1 => (ExpansionMethod::Single, matrix[0][0]),
+            2 => (
+                ExpansionMethod::Cross,
+                matrix[0][0] * matrix[1][1] - matrix[0][1] * matrix[1][0],
+            ),
+            3 => (
+                ExpansionMethod::Sarrus,
+                sum(&sarrus_terms(&matrix).map(|(sign, [a, b, c])| sign * a * b * c)),
+            ),
+            _ => {
+                let minors = (0..size)
+                    .filter(|&col| !matrix[0][col].is_zero())
+                    .map(|col| {
+                        let sign = if col % 2 == 0 { T::one() } else { -T::one() };
+                        let minor = matrix[1..]
+                            .iter()
+                            .map(|line| {
+                                line.iter()
+                                    .enumerate()
+                                    .filter(|&(c, _)| c != col)
+                                    .map(|(_, &value)| value)
+                                    .collect()
+                            })
+                            .collect();
+                        Minor {
+                            column: col + 1,
+                            factor: sign * matrix[0][col],
+                            expansion: Self::new(minor),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let value = sum(&minors
+                    .iter()
+                    .map(|minor| minor.factor * minor.expansion.value)
+                    .collect::<Vec<_>>());
+                (ExpansionMethod::Laplace(minors), value)
+            }
+        };
+        Self {
+            matrix,
+            method,
+            value,
+        }
+    }
+}
+
+/// The six products of the rule of Sarrus together with their sign
+fn sarrus_terms<T: Scalar>(m: &[Vec<T>]) -> [(T, [T; 3]); 6] {
+    let diagonal = |col: usize| [m[0][col], m[1][(col + 1) % 3], m[2][(col + 2) % 3]];
+    let anti_diagonal = |col: usize| [m[0][col], m[1][(col + 2) % 3], m[2][(col + 1) % 3]];
+    [
+        (T::one(), diagonal(0)),
+        (T::one(), diagonal(1)),
+        (T::one(), diagonal(2)),
+        (-T::one(), anti_diagonal(2)),
+        (-T::one(), anti_diagonal(0)),
+        (-T::one(), anti_diagonal(1)),
+    ]
+}
+
+fn sum<T: Scalar>(values: &[T]) -> T {
+    values.iter().fold(T::zero(), |acc, &value| acc + value)
+}
+
+/// Writes a factor of a product, putting negative and compound values in parentheses
+fn factor<T: Scalar>(value: T, precision: usize) -> String {
+    let text = value.format(precision);
+    if text.contains(['+', '-', '/']) {
+        format!("({text})")
+    } else {
+        text
+    }
+}
+
+/// Writes terms `coefficient·rest` as a sum, turning the sign of each coefficient into `+` or `-`
+///
+/// Coefficients of one are left out unless `rest` is empty
+fn write_sum<T: Scalar>(terms: &[(T, String)], precision: usize) -> String {
+    let mut result = String::new();
+    for (i, (coefficient, rest)) in terms.iter().enumerate() {
+        let negative = coefficient.is_negative();
+        let coefficient = if negative {
+            -*coefficient
+        } else {
+            *coefficient
+        };
+        let term = if rest.is_empty() {
+            let text = coefficient.format(precision);
+            if text.contains(['+', '-']) {
+                format!("({text})")
+            } else {
+                text
+            }
+        } else if coefficient == T::one() {
+            rest.clone()
+        } else {
+            format!("{}·{rest}", factor(coefficient, precision))
+        };
+        let sign = match (i, negative) {
+            (0, false) => "",
+            (0, true) => "-",
+            (_, false) => " + ",
+            (_, true) => " - ",
+        };
+        result.push_str(sign);
+        result.push_str(&term);
+    }
+    result
+}
+
+impl<T: Scalar> Debug for DeterminantExpansion<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<T: Scalar> Display for DeterminantExpansion<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        let cells = self
+            .matrix
+            .iter()
+            .map(|line| line.iter().map(|&value| value.format(precision)).collect())
+            .collect::<Vec<_>>();
+        fmt_augmented(f, &cells, self.matrix.len())?;
+        let products = |factors: &[T]| {
+            factors
+                .iter()
+                .map(|&value| factor(value, precision))
+                .collect::<Vec<_>>()
+                .join("·")
+        };
+        let lines = match &self.method {
+            ExpansionMethod::Single => Vec::new(),
+            ExpansionMethod::Cross => {
+                let m = &self.matrix;
+                let terms = [
+                    (T::one(), [m[0][0], m[1][1]]),
+                    (-T::one(), [m[0][1], m[1][0]]),
+                ];
+                vec![
+                    write_sum(&terms.map(|(sign, f)| (sign, products(&f))), precision),
+                    write_sum(
+                        &terms.map(|(sign, [a, b])| (sign * a * b, String::new())),
+                        precision,
+                    ),
+                ]
+            }
+            ExpansionMethod::Sarrus => {
+                let terms = sarrus_terms(&self.matrix);
+                vec![
+                    write_sum(&terms.map(|(sign, f)| (sign, products(&f))), precision),
+                    write_sum(
+                        &terms.map(|(sign, [a, b, c])| (sign * a * b * c, String::new())),
+                        precision,
+                    ),
+                ]
+            }
+            // A zero first line leaves no minors, the value below is all there is to show
+            ExpansionMethod::Laplace(minors) if minors.is_empty() => Vec::new(),
+            ExpansionMethod::Laplace(minors) => {
+                let symbolic = minors
+                    .iter()
+                    .map(|minor| {
+                        (
+                            minor.factor,
+                            format!("det(M{}{})", subscript(1), subscript(minor.column)),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                f.write_fmt(format_args!("\ndet = {}", write_sum(&symbolic, precision)))?;
+                for minor in minors {
+                    f.write_fmt(format_args!(
+                        "\n\nM{}{} =",
+                        subscript(1),
+                        subscript(minor.column)
+                    ))?;
+                    Display::fmt(&minor.expansion, f)?;
+                }
+                f.write_str("\n")?;
+                vec![
+                    write_sum(
+                        &minors
+                            .iter()
+                            .map(|minor| (minor.factor, factor(minor.expansion.value, precision)))
+                            .collect::<Vec<_>>(),
+                        precision,
+                    ),
+                    write_sum(
+                        &minors
+                            .iter()
+                            .map(|minor| (minor.factor * minor.expansion.value, String::new()))
+                            .collect::<Vec<_>>(),
+                        precision,
+                    ),
+                ]
+            }
+        };
+        let mut prefix = "det";
+        for line in lines {
+            f.write_fmt(format_args!("\n{prefix} = {line}"))?;
+            prefix = "   ";
+        }
+        f.write_fmt(format_args!(
+            "\n{prefix} = {}",
+            self.value.format(precision)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::{rational::Rational, with_result::LineMatrix};
+
+    #[test]
+    fn elimination_tracks_swaps() {
+        let matrix = LineMatrix::new(
+            [[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [2.0, 0.0, 3.0]],
+            [0.0; 3],
+        );
+        let elimination = matrix.to_row_echelon();
+        assert!(elimination
+            .operations()
+            .any(|operation| matches!(operation, RowOperation::Swap { .. })));
+        let determinant: f64 = elimination.determinant();
+        assert!((determinant - -8.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn singular_matrices_have_determinant_zero() {
+        let matrix = LineMatrix::from_integers([[1, 2, 3], [2, 4, 6], [0, 1, 1]], [0; 3]);
+        assert_eq!(matrix.determinant(), Rational::ZERO);
+    }
+
+    #[test]
+    fn expansion_picks_the_method_by_size() {
+        let expansion = DeterminantExpansion::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        assert!(matches!(expansion.method, ExpansionMethod::Cross));
+        assert_eq!(expansion.value, -2.0);
+        let expansion = DeterminantExpansion::new(vec![
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, 1.0, 0.0],
+            vec![2.0, 0.0, 3.0],
+        ]);
+        assert!(matches!(expansion.method, ExpansionMethod::Sarrus));
+        assert_eq!(expansion.value, -8.0);
+        let identity = (0..4)
+            .map(|line| (0..4).map(|col| f64::from(line == col)).collect())
+            .collect();
+        let expansion = DeterminantExpansion::new(identity);
+        let ExpansionMethod::Laplace(minors) = &expansion.method else {
+            panic!("4×4 matrices are expanded along their first line");
+        };
+        assert_eq!(minors.len(), 1);
+        assert_eq!(expansion.value, 1.0);
+    }
+
+    #[test]
+    fn writes_the_calculation_like_by_hand() {
+        let expansion = DeterminantExpansion::new(vec![vec![1.0, 2.0], vec![3.0, -4.0]]);
+        assert_eq!(
+            expansion.to_string(),
+            "\n┌╴    ╶┐\n│ 1  2 │\n│ 3 -4 │\n└╴    ╶┘\ndet = 1·(-4) - 2·3\n    = -4 - 6\n    = -10"
+        );
+    }
+
+    #[test]
+    fn laplace_expansion_of_a_zero_first_line_is_zero() {
+        let mut matrix = vec![vec![1.0; 4]; 4];
+        matrix[0] = vec![0.0; 4];
+        let expansion = DeterminantExpansion::new(matrix);
+        assert_eq!(expansion.value, 0.0);
+        assert!(expansion
+            .to_string()
+            .ends_with("│ 1 1 1 1 │\n└╴       ╶┘\ndet = 0"));
+    }
+}
@@ -0,0 +1,706 @@
+use super::{
+    elimination::{reduce, LineStorage, Pivoting, RowOperation, Unrecorded},
+    error::{RowOperationError, SizeMismatch},
+    variable::Variable,
+    Line, LineMatrix,
+};
+use crate::{
+    exporters::excalidraw::{Drawable, ExcalidrawFile},
+    matrix::{
+        draw_augmented, draw_augmented_with_header, fmt_augmented, fmt_augmented_with_header,
+        scalar::Scalar,
+    },
+};
+use std::{
+    fmt::{Debug, Display},
+    ops::{Index, IndexMut},
+};
+
+/// A square matrix with a size chosen at runtime and a result column
+///
+/// The heap-backed counterpart of [`LineMatrix`] with the same row operations
+///
+/// **Indexing is 1-based**
+#[derive(Clone, PartialEq)]
+pub struct DynLineMatrix<T = f64>(Vec<DynLine<T>>, Option<Vec<Variable>>);
+
+impl<T: Scalar> DynLineMatrix<T> {
+    /// Panics if the matrix is not square or the result column has a different size
+    pub fn new(matrix: Vec<Vec<T>>, result: Vec<T>) -> Self {
+        Self::try_new(matrix, result).expect("Invalid matrix size")
+    }
+
+    pub fn try_new(matrix: Vec<Vec<T>>, result: Vec<T>) -> Result<Self, SizeMismatch> {
+        let size = matrix.len();
+        if result.len() != size {
+            return Err(SizeMismatch::new(size, result.len()));
+        }
+        if let Some(line) = matrix.iter().find(|line| line.len() != size) {
+            return Err(SizeMismatch::new(size, line.len()));
+        }
+        Ok(Self(
+            matrix
+                .into_iter()
+                .zip(result)
+                .map(|(line, result)| DynLine::new(line, result))
+                .collect(),
+            None,
+        ))
+    }
+
+    /// Labels every coefficient column with its variable like
+    /// [`AugmentedMatrix::with_labels`](super::AugmentedMatrix::with_labels)
+    ///
+    /// Panics unless there is one label per column
+    pub fn with_labels(mut self, labels: Vec<Variable>) -> Self {
+        if labels.len() != self.size() {
+            panic!("Invalid label count");
+        }
+        self.1 = Some(labels);
+        self
+    }
+
+    pub fn without_labels(mut self) -> Self {
+        self.1 = None;
+        self
+    }
+
+    pub fn labels(&self) -> Option<&[Variable]> {
+        self.1.as_deref()
+    }
+
+    pub fn size(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn apply(self, operation: RowOperation<T>) -> Self {
+        operation.apply(self)
+    }
+
+    pub fn try_apply(self, operation: RowOperation<T>) -> Result<Self, RowOperationError> {
+        operation.try_apply(self)
+    }
+
+    pub fn mul(self, line: usize, value: T) -> Self {
+        self.apply(RowOperation::Mul { line, value })
+    }
+
+    /// Divides a line by `value`, panicking if it is zero
+    pub fn div(self, line: usize, value: T) -> Self {
+        self.apply(RowOperation::Div { line, value })
+    }
+
+    pub fn swap(self, line: usize, other_line: usize) -> Self {
+        self.apply(RowOperation::Swap { line, other_line })
+    }
+
+    /// Swaps two coefficient columns, which swaps the variables they belong to
+    pub fn swap_columns(self, column: usize, other_column: usize) -> Self {
+        self.apply(RowOperation::SwapColumns {
+            column,
+            other_column,
+        })
+    }
+
+    pub fn add_to(self, line: usize, target_line: usize) -> Self {
+        self.apply(RowOperation::AddTo { line, target_line })
+    }
+
+    pub fn sub_to(self, line: usize, target_line: usize) -> Self {
+        self.apply(RowOperation::SubTo { line, target_line })
+    }
+
+    pub fn mul_to(self, line: usize, target_line: usize, value: T) -> Self {
+        self.apply(RowOperation::MulTo {
+            line,
+            target_line,
+            value,
+        })
+    }
+
+    pub fn div_to(self, line: usize, target_line: usize, value: T) -> Self {
+        self.apply(RowOperation::DivTo {
+            line,
+            target_line,
+            value,
+        })
+    }
+
+    pub fn try_mul(self, line: usize, value: T) -> Result<Self, RowOperationError> {
+        self.try_apply(RowOperation::Mul { line, value })
+    }
+
+    pub fn try_div(self, line: usize, value: T) -> Result<Self, RowOperationError> {
+        self.try_apply(RowOperation::Div { line, value })
+    }
+
+    pub fn try_swap(self, line: usize, other_line: usize) -> Result<Self, RowOperationError> {
+        self.try_apply(RowOperation::Swap { line, other_line })
+    }
+
+    pub fn try_swap_columns(
+        self,
+        column: usize,
+        other_column: usize,
+    ) -> Result<Self, RowOperationError> {
+        self.try_apply(RowOperation::SwapColumns {
+            column,
+            other_column,
+        })
+    }
+
+    pub fn try_add_to(self, line: usize, target_line: usize) -> Result<Self, RowOperationError> {
+        self.try_apply(RowOperation::AddTo { line, target_line })
+    }
+
+    pub fn try_sub_to(self, line: usize, target_line: usize) -> Result<Self, RowOperationError> {
+        self.try_apply(RowOperation::SubTo { line, target_line })
+    }
+
+    pub fn try_mul_to(
+        self,
+        line: usize,
+        target_line: usize,
+        value: T,
+    ) -> Result<Self, RowOperationError> {
+        self.try_apply(RowOperation::MulTo {
+            line,
+            target_line,
+            value,
+        })
+    }
+
+    pub fn try_div_to(
+        self,
+        line: usize,
+        target_line: usize,
+        value: T,
+    ) -> Result<Self, RowOperationError> {
+        self.try_apply(RowOperation::DivTo {
+            line,
+            target_line,
+            value,
+        })
+    }
+
+    /// Returns the line or `None` if it is out of range
+    pub fn get(&self, line: usize) -> Option<&DynLine<T>> {
+        self.0.get(line.checked_sub(1)?)
+    }
+
+    /// Returns the line or `None` if it is out of range
+    pub fn get_mut(&mut self, line: usize) -> Option<&mut DynLine<T>> {
+        self.0.get_mut(line.checked_sub(1)?)
+    }
+
+    /// The number of linearly independent lines of the coefficients
+    pub fn rank(&self) -> usize {
+        self.reduce().1.iter().flatten().count()
+    }
+
+    /// A basis of all `x` with `Ax = 0`, one vector per free column
+    ///
+    /// Empty if the columns are linearly independent
+    pub fn null_space(&self) -> Vec<DynVector<T>> {
+        let (reduced, pivots) = self.reduce();
+        (0..self.size())
+            .filter(|&col| pivots[col].is_none())
+            .map(|free| {
+                let mut direction = vec![T::zero(); self.size()];
+                direction[free] = T::one();
+                for (col, pivot) in pivots.iter().enumerate() {
+                    if let Some(line) = pivot {
+                        direction[col] = -reduced[*line].0[free];
+                    }
+                }
+                DynVector(direction)
+            })
+            .collect()
+    }
+
+    /// A basis of all `Ax`, made of the columns of the coefficients that hold a pivot
+    pub fn column_space(&self) -> Vec<DynVector<T>> {
+        let pivots = self.reduce().1;
+        (0..self.size())
+            .filter(|&col| pivots[col].is_some())
+            .map(|col| DynVector(self.0.iter().map(|line| line.0[col]).collect()))
+            .collect()
+    }
+
+    /// Brings the matrix into reduced row echelon form using Gauss-Jordan elimination
+    ///
+    /// Returns the reduced matrix and the line holding the pivot of every column
+    fn reduce(&self) -> (Self, Vec<Option<usize>>) {
+        let mut reduction = Unrecorded(self.clone());
+        reduce(&mut reduction, Pivoting::None);
+        let reduced = reduction.0;
+        let mut pivots = vec![None; self.size()];
+        for (line, values) in reduced.0.iter().enumerate() {
+            if let Some(col) = values.0.iter().position(|value| !value.is_zero()) {
+                pivots[col] = Some(line + 1);
+            }
+        }
+        (reduced, pivots)
+    }
+
+    /// The labels of the columns, empty if there are none
+    fn header(&self) -> Vec<String> {
+        self.1
+            .iter()
+            .flatten()
+            .map(|label| label.to_string())
+            .collect()
+    }
+
+    fn cells(&self, precision: usize) -> Vec<Vec<String>> {
+        self.0
+            .iter()
+            .map(|line| {
+                line.0
+                    .iter()
+                    .chain([&line.1])
+                    .map(|&value| value.format(precision))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<T: Scalar> LineStorage<T> for DynLineMatrix<T> {
+    fn line_count(&self) -> usize {
+        self.size()
+    }
+
+    fn column_count(&self) -> usize {
+        self.size()
+    }
+
+    fn entry(&self, line: usize, column: usize) -> T {
+        self.0[line].0[column]
+    }
+
+    fn values(&self, line: usize) -> Vec<T> {
+        let line = &self.0[line];
+        line.0.iter().copied().chain([line.1]).collect()
+    }
+
+    fn set_values(&mut self, line: usize, values: &[T]) {
+        let (coefficients, result) = values.split_at(self.size());
+        let line = &mut self.0[line];
+        line.0.copy_from_slice(coefficients);
+        line.1 = result[0];
+    }
+
+    fn exchange_lines(&mut self, line: usize, other_line: usize) {
+        self.0.swap(line, other_line);
+    }
+
+    fn exchange_columns(&mut self, column: usize, other_column: usize) {
+        for line in &mut self.0 {
+            line.0.swap(column, other_column);
+        }
+        if let Some(labels) = &mut self.1 {
+            labels.swap(column, other_column);
+        }
+    }
+}
+
+impl<T: Scalar> Index<usize> for DynLineMatrix<T> {
+    type Output = DynLine<T>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("Invalid line")
+    }
+}
+
+impl<T: Scalar> IndexMut<usize> for DynLineMatrix<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("Invalid line")
+    }
+}
+
+impl<T: Scalar> Debug for DynLineMatrix<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<T: Scalar> Display for DynLineMatrix<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        fmt_augmented_with_header(f, &self.header(), &self.cells(precision), self.size())
+    }
+}
+
+impl<T: Scalar> Drawable for DynLineMatrix<T> {
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        let header = self.header();
+        draw_augmented_with_header(file, x, y, locked, &header, &self.cells(3), self.size())
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> From<LineMatrix<SIZE, T>> for DynLineMatrix<T> {
+    fn from(matrix: LineMatrix<SIZE, T>) -> Self {
+        Self(
+            matrix
+                .0
+                .iter()
+                .map(|line| DynLine::new(line.0.to_vec(), line.result()))
+                .collect(),
+            matrix.labels().map(|labels| labels.to_vec()),
+        )
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> TryFrom<DynLineMatrix<T>> for LineMatrix<SIZE, T> {
+    type Error = SizeMismatch;
+
+    fn try_from(matrix: DynLineMatrix<T>) -> Result<Self, Self::Error> {
+        if matrix.size() != SIZE {
+            return Err(SizeMismatch::new(SIZE, matrix.size()));
+        }
+        Ok(Self(
+            std::array::from_fn(|i| {
+                let line = &matrix.0[i];
+                Line::new(std::array::from_fn(|col| line.0[col]), line.1)
+            }),
+            matrix
+                .1
+                .map(|labels| std::array::from_fn(|col| labels[col])),
+        ))
+    }
+}
+
+/// A column vector with a size chosen at runtime
+///
+/// The heap-backed counterpart of [`Solution`](super::solution::Solution)
+///
+/// **Indexing is 1-based**
+#[derive(Clone, PartialEq)]
+#[repr(transparent)]
+pub struct DynVector<T = f64>(Vec<T>);
+
+impl<T: Scalar> DynVector<T> {
+    pub fn new(values: Vec<T>) -> Self {
+        Self(values)
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.0
+    }
+
+    fn cells(&self, precision: usize) -> Vec<Vec<String>> {
+        self.0
+            .iter()
+            .map(|&value| vec![value.format(precision)])
+            .collect()
+    }
+}
+
+impl<T> From<DynVector<T>> for Vec<T> {
+    fn from(vector: DynVector<T>) -> Self {
+        vector.0
+    }
+}
+
+impl<T> Index<usize> for DynVector<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        index
+            .checked_sub(1)
+            .and_then(|index| self.0.get(index))
+            .expect("Invalid line")
+    }
+}
+
+impl<T: Scalar> Debug for DynVector<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<T: Scalar> Display for DynVector<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        fmt_augmented(f, &self.cells(precision), 1)
+    }
+}
+
+impl<T: Scalar> Drawable for DynVector<T> {
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        draw_augmented(file, x, y, locked, &self.cells(3), 1)
+    }
+}
+
+/// A line of a [`DynLineMatrix`] with its result
+#[derive(Clone, PartialEq)]
+pub struct DynLine<T = f64>(Vec<T>, T);
+
+impl<T: Scalar> DynLine<T> {
+    pub fn new(line: Vec<T>, result: T) -> Self {
+        Self(line, result)
+    }
+
+    pub fn coefficients(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn result(&self) -> T {
+        self.1
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.0.iter().all(|value| value.is_finite()) && self.1.is_finite()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::rational::Rational;
+
+    #[test]
+    fn converts_to_and_from_line_matrices() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]);
+        let dynamic = DynLineMatrix::from(matrix);
+        assert_eq!(dynamic.size(), 2);
+        assert_eq!(dynamic[2].coefficients(), &[3.0, 4.0]);
+        assert_eq!(dynamic[2].result(), 6.0);
+        let back = LineMatrix::<2>::try_from(dynamic.clone()).unwrap();
+        assert_eq!(DynLineMatrix::from(back), dynamic);
+        assert!(LineMatrix::<3>::try_from(dynamic).is_err());
+    }
+
+    #[test]
+    fn rejects_matrices_that_are_not_square() {
+        assert!(DynLineMatrix::try_new(vec![vec![1.0, 2.0]], vec![1.0]).is_err());
+        assert!(DynLineMatrix::try_new(vec![vec![1.0]], vec![1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn row_operations_match_the_fixed_size_matrix() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]);
+        let operations = [
+            RowOperation::Swap {
+                line: 1,
+                other_line: 2,
+            },
+            RowOperation::Div {
+                line: 1,
+                value: 3.0,
+            },
+            RowOperation::MulTo {
+                line: 1,
+                target_line: 2,
+                value: -1.0,
+            },
+            RowOperation::SwapColumns {
+                column: 1,
+                other_column: 2,
+            },
+        ];
+        let fixed = operations.into_iter().fold(matrix, LineMatrix::apply);
+        let dynamic = operations
+            .into_iter()
+            .fold(DynLineMatrix::from(matrix), DynLineMatrix::apply);
+        assert_eq!(dynamic, DynLineMatrix::from(fixed));
+    }
+
+    #[test]
+    fn fallible_operations_report_what_is_wrong() {
+        let matrix = DynLineMatrix::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]], vec![5.0, 6.0]);
+        assert_eq!(
+            matrix.clone().try_apply(RowOperation::Mul {
+                line: 3,
+                value: 2.0
+            }),
+            Err(RowOperationError::InvalidLine(3))
+        );
+        assert_eq!(
+            matrix.clone().try_apply(RowOperation::Div {
+                line: 1,
+                value: 0.0
+            }),
+            Err(RowOperationError::DivisionByZero)
+        );
+        assert_eq!(
+            matrix.clone().try_apply(RowOperation::AddTo {
+                line: 2,
+                target_line: 2
+            }),
+            Err(RowOperationError::SameLine(2))
+        );
+        assert_eq!(
+            matrix.try_apply(RowOperation::Mul {
+                line: 1,
+                value: f64::INFINITY
+            }),
+            Err(RowOperationError::NonFinite)
+        );
+    }
+
+    #[test]
+    fn works_with_exact_scalars() {
+        let [zero, one, two, four] = [0, 1, 2, 4].map(Rational::from);
+        let matrix = DynLineMatrix::new(
+            vec![
+                vec![one, two, one],
+                vec![two, four, two],
+                vec![zero, one, one],
+            ],
+            vec![one, two, zero],
+        );
+        assert_eq!(matrix.rank(), 2);
+        let null_space = matrix.null_space();
+        assert_eq!(null_space.len(), 1);
+        assert_eq!(null_space[0].values(), &[one, -one, one]);
+        let column_space = matrix.column_space();
+        assert_eq!(column_space.len(), 2);
+        assert_eq!(column_space[1].values(), &[two, four, one]);
+    }
+
+    #[test]
+    fn spaces_match_the_fixed_size_matrix() {
+        let matrix = LineMatrix::new(
+            [[0.0, 1.0, 2.0], [0.0, 2.0, 4.0], [1.0, 1.0, 1.0]],
+            [0.0; 3],
+        );
+        let dynamic = DynLineMatrix::from(matrix);
+        assert_eq!(dynamic.rank(), matrix.rank());
+        let values =
+            |vectors: Vec<DynVector>| vectors.into_iter().map(Vec::from).collect::<Vec<_>>();
+        assert_eq!(
+            values(dynamic.null_space()),
+            matrix
+                .null_space()
+                .iter()
+                .map(|vector| vector.values().to_vec())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            values(dynamic.column_space()),
+            matrix
+                .column_space()
+                .iter()
+                .map(|vector| vector.values().to_vec())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn labels_follow_column_swaps_and_conversions() {
+        let [x, y] = [Variable::new('x'), Variable::new('y')];
+        let matrix = DynLineMatrix::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]], vec![5.0, 6.0])
+            .with_labels(vec![x, y]);
+        let swapped = matrix.clone().apply(RowOperation::SwapColumns {
+            column: 1,
+            other_column: 2,
+        });
+        assert_eq!(swapped.labels(), Some([y, x].as_slice()));
+        let fixed = LineMatrix::<2>::try_from(swapped.clone()).unwrap();
+        assert_eq!(fixed.labels(), Some(&[y, x]));
+        assert_eq!(DynLineMatrix::from(fixed), swapped);
+        assert_eq!(matrix.without_labels().labels(), None);
+    }
+
+    #[test]
+    fn shows_the_labels_above_the_columns() {
+        let matrix = DynLineMatrix::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]], vec![5.0, 6.0]);
+        let labelled = matrix
+            .clone()
+            .with_labels(vec![Variable::new('x'), Variable::new('y')]);
+        assert_eq!(labelled.to_string(), format!("\n  x y{}", matrix));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid label count")]
+    fn rejects_missing_labels() {
+        DynLineMatrix::new(vec![vec![1.0]], vec![1.0]).with_labels(Vec::new());
+    }
+
+    #[test]
+    fn named_row_operations_match_the_fixed_size_matrix() {
+        let matrix = LineMatrix::new([[2.0, 1.0], [4.0, 3.0]], [3.0, 7.0]);
+        let fixed = matrix
+            .div(1, 2.0)
+            .mul_to(1, 2, -4.0)
+            .swap(1, 2)
+            .swap_columns(1, 2)
+            .mul(2, 3.0)
+            .add_to(1, 2)
+            .sub_to(2, 1)
+            .div_to(2, 1, 4.0);
+        let dynamic = DynLineMatrix::from(matrix)
+            .div(1, 2.0)
+            .mul_to(1, 2, -4.0)
+            .swap(1, 2)
+            .swap_columns(1, 2)
+            .mul(2, 3.0)
+            .add_to(1, 2)
+            .sub_to(2, 1)
+            .div_to(2, 1, 4.0);
+        assert_eq!(dynamic, DynLineMatrix::from(fixed));
+    }
+
+    #[test]
+    fn fallible_named_operations_match_the_fixed_size_matrix() {
+        let matrix = LineMatrix::new([[2.0, 1.0], [4.0, 3.0]], [3.0, 7.0]);
+        let dynamic = DynLineMatrix::from(matrix);
+        let results = [
+            (
+                matrix.try_mul(1, 2.0).map(DynLineMatrix::from),
+                dynamic.clone().try_mul(1, 2.0),
+            ),
+            (
+                matrix.try_div(3, 2.0).map(DynLineMatrix::from),
+                dynamic.clone().try_div(3, 2.0),
+            ),
+            (
+                matrix.try_swap(1, 2).map(DynLineMatrix::from),
+                dynamic.clone().try_swap(1, 2),
+            ),
+            (
+                matrix.try_swap_columns(1, 0).map(DynLineMatrix::from),
+                dynamic.clone().try_swap_columns(1, 0),
+            ),
+            (
+                matrix.try_add_to(1, 1).map(DynLineMatrix::from),
+                dynamic.clone().try_add_to(1, 1),
+            ),
+            (
+                matrix.try_sub_to(2, 1).map(DynLineMatrix::from),
+                dynamic.clone().try_sub_to(2, 1),
+            ),
+            (
+                matrix.try_mul_to(1, 2, -2.0).map(DynLineMatrix::from),
+                dynamic.clone().try_mul_to(1, 2, -2.0),
+            ),
+            (
+                matrix.try_div_to(1, 2, 0.0).map(DynLineMatrix::from),
+                dynamic.try_div_to(1, 2, 0.0),
+            ),
+        ];
+        for (fixed, dynamic) in results {
+            assert_eq!(dynamic, fixed);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid line")]
+    fn named_operations_panic_on_invalid_lines() {
+        DynLineMatrix::new(vec![vec![1.0]], vec![1.0]).swap(2, 1);
+    }
+
+    #[test]
+    fn vectors_are_indexed_from_one() {
+        let vector = DynVector::new(vec![1.0, 2.0]);
+        assert_eq!((vector[1], vector[2]), (1.0, 2.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid line")]
+    fn vector_index_zero_is_invalid() {
+        let _ = DynVector::new(vec![1.0, 2.0])[0];
+    }
+}
@@ -0,0 +1,755 @@
+use super::{
+    elimination::Elimination,
+    error::NoConvergence,
+    solution::{subscript, Solution},
+    AugmentedMatrix, Line,
+};
+use crate::{
+    exporters::excalidraw::{Drawable, ExcalidrawFile},
+    matrix::{complex::Complex, draw_labelled, format_entry, scalar::Scalar},
+};
+use std::fmt::{Debug, Display};
+
+/// Subdiagonal entries below this relative to the diagonal entries next to them are treated as
+/// zero by the QR iteration
+const QR_TOLERANCE: f64 = f64::EPSILON;
+
+/// The QR iteration gives up if it takes this many iterations to split off an eigenvalue
+const QR_ITERATIONS: usize = 100;
+
+/// Discriminants of a cubic below this relative to the size of their terms are treated as zero,
+/// so roots that only differ by rounding errors come out as one repeated root
+const DISCRIMINANT_TOLERANCE: f64 = 1e-12;
+
+/// A polynomial in `λ` with real coefficients
+#[derive(Clone, PartialEq)]
+pub struct Polynomial(Vec<f64>);
+
+impl Polynomial {
+    /// The coefficients start with the constant term
+    pub fn new(coefficients: Vec<f64>) -> Self {
+        Self(coefficients)
+    }
+
+    /// The coefficients, starting with the constant term
+    pub fn coefficients(&self) -> &[f64] {
+        &self.0
+    }
+
+    /// The highest power with a non-zero coefficient
+    pub fn degree(&self) -> usize {
+        self.0
+            .iter()
+            .rposition(|value| !value.is_zero())
+            .unwrap_or(0)
+    }
+
+    pub fn evaluate(&self, x: f64) -> f64 {
+        self.0.iter().rev().fold(0.0, |acc, &value| acc * x + value)
+    }
+
+    /// All roots of a polynomial of degree 3 or lower, calculated with the closed formulas
+    ///
+    /// Returns `None` for higher degrees
+    pub fn roots(&self) -> Option<Vec<Complex>> {
+        let degree = self.degree();
+        let leading = self.0.get(degree).copied().unwrap_or(0.0);
+        let c = |power: usize| self.0[power] / leading;
+        let mut roots = match degree {
+            0 => Vec::new(),
+            1 => vec![Complex::from(-c(0))],
+            2 => quadratic_roots(c(1), c(0)).to_vec(),
+            3 => cubic_roots(c(2), c(1), c(0)).to_vec(),
+            _ => return None,
+        };
+        sort_roots(&mut roots);
+        Some(roots)
+    }
+}
+
+/// The roots of `λ² + bλ + c`
+fn quadratic_roots(b: f64, c: f64) -> [Complex; 2] {
+    let discriminant = b * b / 4.0 - c;
+    if discriminant < 0.0 {
+        let im = (-discriminant).sqrt();
+        [Complex::new(-b / 2.0, im), Complex::new(-b / 2.0, -im)]
+    } else {
+        let root = discriminant.sqrt();
+        [
+            Complex::from(-b / 2.0 + root),
+            Complex::from(-b / 2.0 - root),
+        ]
+    }
+}
+
+/// The roots of `λ³ + aλ² + bλ + c` using the formula of Cardano
+///
+/// With three real roots the trigonometric form is used instead to avoid complex cube roots
+fn cubic_roots(a: f64, b: f64, c: f64) -> [Complex; 3] {
+    let shift = a / 3.0;
+    let p = b - a * a / 3.0;
+    let q = 2.0 * a * a * a / 27.0 - a * b / 3.0 + c;
+    let discriminant = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+    let tolerance = DISCRIMINANT_TOLERANCE * (q / 2.0).powi(2).max((p / 3.0).abs().powi(3));
+    if discriminant > tolerance {
+        let u = (-q / 2.0 + discriminant.sqrt()).cbrt();
+        let v = (-q / 2.0 - discriminant.sqrt()).cbrt();
+        let re = -(u + v) / 2.0 - shift;
+        let im = 3f64.sqrt() / 2.0 * (u - v);
+        [
+            Complex::from(u + v - shift),
+            Complex::new(re, im),
+            Complex::new(re, -im),
+        ]
+    } else if p.is_zero() {
+        [Complex::from(-shift); 3]
+    } else if discriminant >= -tolerance {
+        let double = -3.0 * q / (2.0 * p) - shift;
+        [
+            Complex::from(3.0 * q / p - shift),
+            Complex::from(double),
+            Complex::from(double),
+        ]
+    } else {
+        // A negative discriminant needs a negative `p`, clamped against rounding errors
+        let p = p.min(0.0);
+        let r = (-p / 3.0).sqrt();
+        let phi = (3.0 * q / (2.0 * p) * (-3.0 / p).sqrt())
+            .clamp(-1.0, 1.0)
+            .acos()
+            / 3.0;
+        std::array::from_fn(|k| {
+            let angle = phi - 2.0 * std::f64::consts::PI * k as f64 / 3.0;
+            Complex::from(2.0 * r * angle.cos() - shift)
+        })
+    }
+}
+
+/// Sorts by descending real part, putting the positive imaginary part of a pair first
+fn sort_roots(roots: &mut [Complex]) {
+    roots.sort_by(|a, b| b.re.total_cmp(&a.re).then(b.im.total_cmp(&a.im)));
+}
+
+/// Writes a number with superscript digits, like in `λ²`
+fn superscript(number: usize) -> String {
+    number
+        .to_string()
+        .chars()
+        .map(|digit| match digit {
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect()
+}
+
+impl Debug for Polynomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl Display for Polynomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        let mut first = true;
+        for (power, &value) in self.0.iter().enumerate().rev() {
+            if value.is_zero() {
+                continue;
+            }
+            let sign = match (first, value < 0.0) {
+                (true, false) => "",
+                (true, true) => "-",
+                (false, false) => " + ",
+                (false, true) => " - ",
+            };
+            let coefficient = format_entry(value.abs(), precision);
+            let coefficient = if coefficient == "1" && power > 0 {
+                ""
+            } else {
+                coefficient.as_str()
+            };
+            let variable = match power {
+                0 => String::new(),
+                1 => String::from("λ"),
+                _ => format!("λ{}", superscript(power)),
+            };
+            f.write_fmt(format_args!("{sign}{coefficient}{variable}"))?;
+            first = false;
+        }
+        if first {
+            f.write_str("0")?;
+        }
+        Ok(())
+    }
+}
+
+/// The eigenvalues of a matrix and how they were found
+#[derive(Clone, PartialEq)]
+pub struct Eigenvalues {
+    /// `det(λI - A)`, only set for sizes up to 3×3 where its roots are calculated exactly
+    pub polynomial: Option<Polynomial>,
+    /// Sorted by descending real part, repeated according to their multiplicity
+    pub values: Vec<Complex>,
+}
+
+impl<const SIZE: usize, const RESULTS: usize> AugmentedMatrix<SIZE, SIZE, RESULTS> {
+    /// The characteristic polynomial `det(λI - A)` of the coefficients
+    ///
+    /// Calculated with the Faddeev–LeVerrier algorithm, which only needs matrix products and
+    /// traces
+    pub fn characteristic_polynomial(&self) -> Polynomial {
+        let a = self.0.map(|line| line.0);
+        let mut coefficients = vec![0.0; SIZE + 1];
+        coefficients[SIZE] = 1.0;
+        let mut m = [[0.0; SIZE]; SIZE];
+        for k in 1..=SIZE {
+            let mut next = multiply(&a, &m);
+            for (i, line) in next.iter_mut().enumerate() {
+                line[i] += coefficients[SIZE - k + 1];
+            }
+            m = next;
+            let trace = (0..SIZE)
+                .map(|i| (0..SIZE).map(|j| a[i][j] * m[j][i]).sum::<f64>())
+                .sum::<f64>();
+            coefficients[SIZE - k] = -trace / k as f64;
+        }
+        Polynomial(coefficients)
+    }
+
+    /// Calculates the eigenvalues of the coefficients
+    ///
+    /// Up to 3×3 these are the exact roots of the characteristic polynomial, larger matrices
+    /// use the QR iteration with Francis double shifts, which fails if it does not converge
+    pub fn eigenvalues(&self) -> Result<Eigenvalues, NoConvergence> {
+        if SIZE <= 3 {
+            let polynomial = self.characteristic_polynomial();
+            let values = polynomial.roots().unwrap_or_default();
+            return Ok(Eigenvalues {
+                polynomial: Some(polynomial),
+                values,
+            });
+        }
+        let mut values = francis_qr(hessenberg(self.0.map(|line| line.0)))?;
+        sort_roots(&mut values);
+        Ok(Eigenvalues {
+            polynomial: None,
+            values,
+        })
+    }
+}
+
+fn multiply<const SIZE: usize>(
+    a: &[[f64; SIZE]; SIZE],
+    b: &[[f64; SIZE]; SIZE],
+) -> [[f64; SIZE]; SIZE] {
+    std::array::from_fn(|i| std::array::from_fn(|j| (0..SIZE).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+/// Brings `a` into upper Hessenberg form with the same eigenvalues using Householder
+/// reflections, so every entry below the first subdiagonal is zero
+fn hessenberg<const SIZE: usize>(mut a: [[f64; SIZE]; SIZE]) -> [[f64; SIZE]; SIZE] {
+    for col in 0..SIZE.saturating_sub(2) {
+        let norm = (col + 1..SIZE)
+            .map(|i| a[i][col] * a[i][col])
+            .sum::<f64>()
+            .sqrt();
+        if norm == 0.0 {
+            continue;
+        }
+        let alpha = if a[col + 1][col] > 0.0 { -norm } else { norm };
+        let v: [f64; SIZE] = std::array::from_fn(|i| match i {
+            _ if i <= col => 0.0,
+            _ if i == col + 1 => a[i][col] - alpha,
+            _ => a[i][col],
+        });
+        let length = v.iter().map(|x| x * x).sum::<f64>();
+        if length == 0.0 {
+            continue;
+        }
+        // a := (I - 2vvᵀ/vᵀv) a (I - 2vvᵀ/vᵀv)
+        let dots: [f64; SIZE] =
+            std::array::from_fn(|j| (0..SIZE).map(|i| v[i] * a[i][j]).sum::<f64>() * 2.0 / length);
+        for (line, &v) in a.iter_mut().zip(&v) {
+            for (value, dot) in line.iter_mut().zip(dots) {
+                *value -= dot * v;
+            }
+        }
+        for line in &mut a {
+            let dot = line.iter().zip(&v).map(|(a, b)| a * b).sum::<f64>() * 2.0 / length;
+            for (value, &v) in line.iter_mut().zip(&v) {
+                *value -= dot * v;
+            }
+        }
+    }
+    a
+}
+
+/// The eigenvalues of an upper Hessenberg matrix using the QR iteration with Francis double
+/// shifts
+///
+/// Every time a subdiagonal entry becomes negligible the eigenvalues of the 1×1 or 2×2 block
+/// below it are split off and the iteration continues on the rest of the matrix. Fails if an
+/// eigenvalue takes more than [`QR_ITERATIONS`] iterations
+fn francis_qr<const SIZE: usize>(
+    mut a: [[f64; SIZE]; SIZE],
+) -> Result<Vec<Complex>, NoConvergence> {
+    let norm = (0..SIZE)
+        .flat_map(|i| (i.saturating_sub(1)..SIZE).map(move |j| (i, j)))
+        .map(|(i, j)| a[i][j].abs())
+        .sum::<f64>();
+    let mut values = Vec::with_capacity(SIZE);
+    // The sum of the exceptional shifts already subtracted from the diagonal
+    let mut shift = 0.0;
+    let mut iterations = 0;
+    // The active block is `0..=last`
+    let mut size = SIZE;
+    while size > 0 {
+        let last = size - 1;
+        // The first line of the unreduced block that ends at `last`
+        let mut first = last;
+        while first > 0 {
+            let mut scale = a[first - 1][first - 1].abs() + a[first][first].abs();
+            if scale == 0.0 {
+                scale = norm;
+            }
+            if a[first][first - 1].abs() <= QR_TOLERANCE * scale {
+                a[first][first - 1] = 0.0;
+                break;
+            }
+            first -= 1;
+        }
+        let mut x = a[last][last];
+        if first == last {
+            values.push(Complex::from(x + shift));
+            size -= 1;
+            iterations = 0;
+            continue;
+        }
+        let mut y = a[last - 1][last - 1];
+        let mut w = a[last][last - 1] * a[last - 1][last];
+        if first == last - 1 {
+            let p = (y - x) / 2.0;
+            let q = p * p + w;
+            let z = q.abs().sqrt();
+            let x = x + shift;
+            if q >= 0.0 {
+                let z = if p >= 0.0 { p + z } else { p - z };
+                let other = if z == 0.0 { x + z } else { x - w / z };
+                values.extend([Complex::from(x + z), Complex::from(other)]);
+            } else {
+                values.extend([Complex::new(x + p, z), Complex::new(x + p, -z)]);
+            }
+            size -= 2;
+            iterations = 0;
+            continue;
+        }
+        if iterations == QR_ITERATIONS {
+            return Err(NoConvergence {
+                iterations: QR_ITERATIONS,
+            });
+        }
+        // Exceptional shifts get the iteration out of cycles, like for permutation matrices
+        if iterations > 0 && iterations % 10 == 0 {
+            shift += x;
+            for (i, line) in a.iter_mut().enumerate().take(size) {
+                line[i] -= x;
+            }
+            let s = a[last][last - 1].abs() + a[last - 1][last - 2].abs();
+            x = 0.75 * s;
+            y = x;
+            w = -0.4375 * s * s;
+        }
+        iterations += 1;
+        // Look for two consecutive small subdiagonal entries to start the step below them
+        let mut start = last - 2;
+        let (mut p, mut q, mut r);
+        loop {
+            let z = a[start][start];
+            let (rx, sy) = (x - z, y - z);
+            p = (rx * sy - w) / a[start + 1][start] + a[start][start + 1];
+            q = a[start + 1][start + 1] - z - rx - sy;
+            r = a[start + 2][start + 1];
+            let scale = p.abs() + q.abs() + r.abs();
+            p /= scale;
+            q /= scale;
+            r /= scale;
+            if start == first {
+                break;
+            }
+            let u = a[start][start - 1].abs() * (q.abs() + r.abs());
+            let v =
+                p.abs() * (a[start - 1][start - 1].abs() + z.abs() + a[start + 1][start + 1].abs());
+            if u <= QR_TOLERANCE * v {
+                break;
+            }
+            start -= 1;
+        }
+        for i in start..last - 1 {
+            a[i + 2][i] = 0.0;
+            if i != start {
+                a[i + 2][i - 1] = 0.0;
+            }
+        }
+        // Chase the bulge down the subdiagonal with 3×3 Householder reflections
+        for k in start..last {
+            let mut scale = 0.0;
+            if k != start {
+                p = a[k][k - 1];
+                q = a[k + 1][k - 1];
+                r = if k + 1 != last { a[k + 2][k - 1] } else { 0.0 };
+                scale = p.abs() + q.abs() + r.abs();
+                if scale != 0.0 {
+                    p /= scale;
+                    q /= scale;
+                    r /= scale;
+                }
+            }
+            let root = (p * p + q * q + r * r).sqrt();
+            let s = if p >= 0.0 { root } else { -root };
+            if s == 0.0 {
+                continue;
+            }
+            if k == start {
+                if first != start {
+                    a[k][k - 1] = -a[k][k - 1];
+                }
+            } else {
+                a[k][k - 1] = -s * scale;
+            }
+            p += s;
+            let (x, y, z) = (p / s, q / s, r / s);
+            q /= p;
+            r /= p;
+            for j in k..=last {
+                let mut p = a[k][j] + q * a[k + 1][j];
+                if k + 1 != last {
+                    p += r * a[k + 2][j];
+                    a[k + 2][j] -= p * z;
+                }
+                a[k + 1][j] -= p * y;
+                a[k][j] -= p * x;
+            }
+            for line in a.iter_mut().take(last.min(k + 3) + 1).skip(first) {
+                let mut p = x * line[k] + y * line[k + 1];
+                if k + 1 != last {
+                    p += z * line[k + 2];
+                    line[k + 2] -= p * r;
+                }
+                line[k + 1] -= p * q;
+                line[k] -= p;
+            }
+        }
+    }
+    Ok(values)
+}
+
+impl Debug for Eigenvalues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl Display for Eigenvalues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        match &self.polynomial {
+            Some(polynomial) => {
+                f.write_str("det(λI - A) = ")?;
+                Display::fmt(polynomial, f)?;
+            }
+            None => f.write_str("QR iteration")?,
+        }
+        for (i, value) in self.values.iter().enumerate() {
+            f.write_fmt(format_args!(
+                "\nλ{} = {}",
+                subscript(i + 1),
+                value.format(precision)
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+/// The solutions of `(A - λI)x = 0` for an eigenvalue `λ`
+#[derive(Clone)]
+pub struct Eigenspace<const SIZE: usize, T = f64> {
+    pub eigenvalue: T,
+    /// The Gauss-Jordan elimination of `(A - λI | 0)`
+    pub elimination: Elimination<SIZE, SIZE, 1, T>,
+    /// One eigenvector per free column, empty if `λ` is no eigenvalue
+    pub basis: Vec<Solution<SIZE, T>>,
+}
+
+impl<const SIZE: usize, const RESULTS: usize, T: Scalar> AugmentedMatrix<SIZE, SIZE, RESULTS, T> {
+    /// Finds the eigenvectors of `eigenvalue` by eliminating `(A - λI | 0)`
+    ///
+    /// Complex eigenvalues of a real matrix need the matrix to be mapped to
+    /// [`Complex`] first
+    pub fn eigenspace(&self, eigenvalue: T) -> Eigenspace<SIZE, T> {
+        let shifted = AugmentedMatrix(
+            std::array::from_fn(|i| {
+                let mut line = self.0[i].0;
+                line[i] -= eigenvalue;
+                Line(line, [T::zero()])
+            }),
+            self.1,
+        );
+        let elimination = shifted.to_reduced_row_echelon();
+        let reduced = elimination.result();
+        let basis = reduced
+            .free_variables(&reduced.pivot_lines())
+            .into_iter()
+            .map(|free| free.direction)
+            .collect();
+        Eigenspace {
+            eigenvalue,
+            elimination,
+            basis,
+        }
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Debug for Eigenspace<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Display for Eigenspace<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        f.write_fmt(format_args!("λ = {}", self.eigenvalue.format(precision)))?;
+        Display::fmt(&self.elimination, f)?;
+        for (i, vector) in self.basis.iter().enumerate() {
+            f.write_fmt(format_args!("\nv{} =", subscript(i + 1)))?;
+            Display::fmt(vector, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Drawable for Eigenspace<SIZE, T> {
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        let scale = file.app_state.grid_size;
+        let (width, height) = self.elimination.draw(file, x, y, locked);
+        if self.basis.is_empty() {
+            return (width, height);
+        }
+        let labels = (1..=self.basis.len())
+            .map(|i| format!("v{} =", subscript(i)))
+            .collect::<Vec<_>>();
+        let vectors = labels
+            .iter()
+            .zip(&self.basis)
+            .map(|(label, vector)| (label.as_str(), vector as &dyn Drawable))
+            .collect::<Vec<_>>();
+        let (vectors_width, _) = draw_labelled(file, x + width + scale * 2, y, locked, &vectors);
+        (width + scale * 2 + vectors_width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::with_result::LineMatrix;
+
+    fn assert_roots(roots: &[Complex], expected: &[Complex]) {
+        assert_eq!(roots.len(), expected.len());
+        for (root, expected) in roots.iter().zip(expected) {
+            assert!(
+                (*root - *expected).norm() < 1e-9,
+                "{roots:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn characteristic_polynomial_of_a_triangular_matrix() {
+        let matrix = LineMatrix::new([[2.0, 1.0], [0.0, 3.0]], [0.0; 2]);
+        let polynomial = matrix.characteristic_polynomial();
+        assert_eq!(polynomial.coefficients(), &[6.0, -5.0, 1.0]);
+        assert_eq!(polynomial.to_string(), "λ² - 5λ + 6");
+        assert_eq!(polynomial.evaluate(2.0), 0.0);
+    }
+
+    #[test]
+    fn quadratic_roots_can_be_complex() {
+        let roots = Polynomial::new(vec![1.0, 0.0, 1.0]).roots().unwrap();
+        assert_roots(&roots, &[Complex::I, -Complex::I]);
+    }
+
+    #[test]
+    fn cubic_roots_for_every_sign_of_the_discriminant() {
+        let cases = [
+            // (λ - 1)(λ - 2)(λ - 3)
+            (
+                vec![-6.0, 11.0, -6.0, 1.0],
+                [3.0, 2.0, 1.0].map(Complex::from),
+            ),
+            // (λ - 1)²(λ - 2)
+            (
+                vec![-2.0, 5.0, -4.0, 1.0],
+                [2.0, 1.0, 1.0].map(Complex::from),
+            ),
+            // (λ - 2)³
+            (vec![-8.0, 12.0, -6.0, 1.0], [2.0; 3].map(Complex::from)),
+            // (λ - 1)(λ² + 1)
+            (
+                vec![-1.0, 1.0, -1.0, 1.0],
+                [Complex::ONE, Complex::I, -Complex::I],
+            ),
+        ];
+        for (coefficients, expected) in cases {
+            assert_roots(&Polynomial::new(coefficients).roots().unwrap(), &expected);
+        }
+    }
+
+    #[test]
+    fn small_complex_eigenvalues_are_not_lost() {
+        let matrix = LineMatrix::new(
+            [[0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, -1e-5, 0.0]],
+            [0.0; 3],
+        );
+        let eigenvalues = matrix.eigenvalues().unwrap();
+        assert!(eigenvalues.values.iter().all(|value| value.is_finite()));
+        let im = 1e-5f64.sqrt();
+        assert_roots(
+            &eigenvalues.values,
+            &[Complex::new(0.0, im), Complex::ZERO, Complex::new(0.0, -im)],
+        );
+    }
+
+    #[test]
+    fn larger_matrices_use_the_qr_iteration() {
+        let matrix = LineMatrix::new(
+            [
+                [4.0, 1.0, 0.0, 0.0],
+                [1.0, 3.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, -2.0],
+                [0.0, 0.0, 2.0, 0.0],
+            ],
+            [0.0; 4],
+        );
+        let eigenvalues = matrix.eigenvalues().unwrap();
+        assert!(eigenvalues.polynomial.is_none());
+        let root = 5f64.sqrt() / 2.0;
+        assert_roots(
+            &eigenvalues.values,
+            &[
+                Complex::from(3.5 + root),
+                Complex::from(3.5 - root),
+                Complex::new(0.0, 2.0),
+                Complex::new(0.0, -2.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn eigenspace_spans_the_eigenvectors() {
+        let matrix = LineMatrix::new([[2.0, 1.0], [0.0, 3.0]], [0.0; 2]);
+        let eigenspace = matrix.eigenspace(3.0);
+        assert_eq!(eigenspace.basis.len(), 1);
+        assert_eq!(eigenspace.basis[0].values(), &[1.0, 1.0]);
+        assert!(matrix.eigenspace(1.0).basis.is_empty());
+    }
+
+    /// Checks `A·v = λ·v` for every vector of the eigenspace of every eigenvalue
+    fn assert_eigenvectors<const SIZE: usize>(matrix: LineMatrix<SIZE>) {
+        let complex = matrix.map(Complex::from);
+        let eigenvalues = matrix.eigenvalues().unwrap().values;
+        for &value in &eigenvalues {
+            let basis = complex.eigenspace(value).basis;
+            assert!(!basis.is_empty(), "no eigenvectors for {value}");
+            for vector in basis {
+                for i in 1..=SIZE {
+                    let product = (1..=SIZE)
+                        .map(|j| complex[i].coefficients()[j - 1] * vector[j])
+                        .fold(Complex::ZERO, |sum, x| sum + x);
+                    assert!(
+                        (product - value * vector[i]).norm() < 1e-8,
+                        "A·v != {value}·v for v = {vector}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric_matrices_have_real_eigenvalues() {
+        let matrix = LineMatrix::new(
+            [
+                [1.0, 2.0, 3.0, 4.0],
+                [2.0, 1.0, 0.0, 1.0],
+                [3.0, 0.0, 1.0, 2.0],
+                [4.0, 1.0, 2.0, 1.0],
+            ],
+            [0.0; 4],
+        );
+        let values = matrix.eigenvalues().unwrap().values;
+        assert!(values.iter().all(|value| value.im == 0.0));
+        let sum = values.iter().fold(Complex::ZERO, |sum, &x| sum + x);
+        let product = values.iter().fold(Complex::ONE, |product, &x| product * x);
+        assert!((sum - Complex::from(4.0)).norm() < 1e-9, "{sum}");
+        assert!((product - Complex::from(32.0)).norm() < 1e-9, "{product}");
+        assert_eigenvectors(matrix);
+    }
+
+    #[test]
+    fn permutation_matrices_converge() {
+        let matrix = LineMatrix::new(
+            [
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+                [1.0, 0.0, 0.0, 0.0],
+            ],
+            [0.0; 4],
+        );
+        assert_roots(
+            &matrix.eigenvalues().unwrap().values,
+            &[Complex::ONE, Complex::I, -Complex::I, -Complex::ONE],
+        );
+        assert_eigenvectors(matrix);
+    }
+
+    #[test]
+    fn eigenvalues_of_an_unsymmetric_matrix() {
+        let matrix = LineMatrix::new(
+            [
+                [2.0, -1.0, 0.0, 3.0, 1.0],
+                [1.0, 4.0, -2.0, 0.0, 0.0],
+                [0.0, 5.0, 1.0, -1.0, 2.0],
+                [-3.0, 0.0, 1.0, 2.0, 1.0],
+                [1.0, 1.0, 0.0, -2.0, -1.0],
+            ],
+            [0.0; 5],
+        );
+        let values = matrix.eigenvalues().unwrap().values;
+        let sum = values.iter().fold(Complex::ZERO, |sum, &x| sum + x);
+        let product = values.iter().fold(Complex::ONE, |product, &x| product * x);
+        let determinant: f64 = matrix.determinant();
+        assert!((sum - Complex::from(8.0)).norm() < 1e-9, "{sum}");
+        assert!(
+            (product - Complex::from(determinant)).norm() < 1e-8,
+            "{product} != {determinant}"
+        );
+        assert_eigenvectors(matrix);
+    }
+
+    #[test]
+    fn no_convergence_is_reported() {
+        let error = NoConvergence { iterations: 100 };
+        assert_eq!(
+            error.to_string(),
+            "The QR iteration did not converge within 100 iterations"
+        );
+    }
+}
@@ -0,0 +1,1030 @@
+use super::{
+    condition::{SmallPivot, PIVOT_TOLERANCE},
+    error::RowOperationError,
+    solution::subscript,
+    AugmentedMatrix,
+};
+use crate::{
+    exporters::excalidraw::{Drawable, Element, ExcalidrawFile},
+    matrix::scalar::Scalar,
+};
+use std::fmt::{Debug, Display};
+
+/// A single row operation as it would be written down by hand
+///
+/// **Indexing is 1-based**
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RowOperation<T = f64> {
+    /// `line := line · value`
+    Mul { line: usize, value: T },
+    /// `line := line / value`
+    Div { line: usize, value: T },
+    /// `line <-> other_line`
+    Swap { line: usize, other_line: usize },
+    /// `x_column <-> x_other_column`, renaming the variables of two coefficient columns
+    SwapColumns { column: usize, other_column: usize },
+    /// `target_line := target_line + line`
+    AddTo { line: usize, target_line: usize },
+    /// `target_line := target_line - line`
+    SubTo { line: usize, target_line: usize },
+    /// `target_line := target_line + value · line`
+    MulTo {
+        line: usize,
+        target_line: usize,
+        value: T,
+    },
+    /// `target_line := target_line + line / value`
+    DivTo {
+        line: usize,
+        target_line: usize,
+        value: T,
+    },
+}
+
+/// The lines of a matrix as the row operations see them, **indexing is 0-based**
+///
+/// Every [`RowOperation`] is implemented once on top of this, so [`AugmentedMatrix`] and
+/// [`DynLineMatrix`](super::dynamic::DynLineMatrix) behave the same
+pub trait LineStorage<T> {
+    fn line_count(&self) -> usize;
+
+    /// The number of coefficient columns
+    fn column_count(&self) -> usize;
+
+    /// The coefficient in `column` of `line`
+    fn entry(&self, line: usize, column: usize) -> T;
+
+    /// The coefficients of a line followed by its results
+    fn values(&self, line: usize) -> Vec<T>;
+
+    /// Overwrites a line with values in the order of [`Self::values`]
+    fn set_values(&mut self, line: usize, values: &[T]);
+
+    fn exchange_lines(&mut self, line: usize, other_line: usize);
+
+    /// Exchanges two coefficient columns together with their labels
+    fn exchange_columns(&mut self, column: usize, other_column: usize);
+}
+
+impl<T: Scalar> RowOperation<T> {
+    /// Applies the operation, panicking on invalid lines and divisions by zero
+    pub fn apply<M: LineStorage<T>>(self, mut matrix: M) -> M {
+        let check = |value: usize, count: usize, message: &str| {
+            if !(1..=count).contains(&value) {
+                panic!("{message}");
+            }
+        };
+        let lines = matrix.line_count();
+        if let Self::Div { value, .. } | Self::DivTo { value, .. } = self {
+            if value == T::zero() {
+                panic!("Division by zero");
+            }
+        }
+        match self {
+            Self::Mul { line, .. } | Self::Div { line, .. } => check(line, lines, "Invalid line"),
+            Self::Swap { line, other_line } => {
+                check(line, lines, "Invalid line");
+                check(other_line, lines, "Invalid other line");
+            }
+            Self::SwapColumns {
+                column,
+                other_column,
+            } => {
+                check(column, matrix.column_count(), "Invalid column");
+                check(other_column, matrix.column_count(), "Invalid other column");
+            }
+            Self::AddTo { line, target_line }
+            | Self::SubTo { line, target_line }
+            | Self::MulTo {
+                line, target_line, ..
+            }
+            | Self::DivTo {
+                line, target_line, ..
+            } => {
+                check(line, lines, "Invalid line");
+                check(target_line, lines, "Invalid target line");
+            }
+        }
+        self.perform(&mut matrix);
+        matrix
+    }
+
+    /// Applies the operation, returning an error instead of panicking like [`Self::apply`]
+    ///
+    /// Besides invalid lines and columns this rejects combining a line with itself, divisions
+    /// by zero and results that are not finite
+    pub fn try_apply<M: LineStorage<T>>(self, mut matrix: M) -> Result<M, RowOperationError> {
+        self.check(matrix.line_count(), matrix.column_count())?;
+        self.perform(&mut matrix);
+        if let Some(line) = self.changed_line() {
+            if !matrix
+                .values(line - 1)
+                .iter()
+                .all(|value| value.is_finite())
+            {
+                return Err(RowOperationError::NonFinite);
+            }
+        }
+        Ok(matrix)
+    }
+
+    fn check(&self, lines: usize, columns: usize) -> Result<(), RowOperationError> {
+        let check_line = |line: usize| {
+            if (1..=lines).contains(&line) {
+                Ok(())
+            } else {
+                Err(RowOperationError::InvalidLine(line))
+            }
+        };
+        let check_column = |column: usize| {
+            if (1..=columns).contains(&column) {
+                Ok(())
+            } else {
+                Err(RowOperationError::InvalidColumn(column))
+            }
+        };
+        let check_lines = |line: usize, other_line: usize| {
+            check_line(line)?;
+            check_line(other_line)?;
+            if line == other_line {
+                return Err(RowOperationError::SameLine(line));
+            }
+            Ok(())
+        };
+        let check_divisor = |value: T| {
+            if value == T::zero() {
+                return Err(RowOperationError::DivisionByZero);
+            }
+            Ok(())
+        };
+        match *self {
+            Self::Mul { line, .. } => check_line(line),
+            Self::Div { line, value } => check_line(line).and(check_divisor(value)),
+            Self::SwapColumns {
+                column,
+                other_column,
+            } => check_column(column).and(check_column(other_column)),
+            Self::Swap { line, other_line } => check_lines(line, other_line),
+            Self::AddTo { line, target_line }
+            | Self::SubTo { line, target_line }
+            | Self::MulTo {
+                line, target_line, ..
+            } => check_lines(line, target_line),
+            Self::DivTo {
+                line,
+                target_line,
+                value,
+            } => check_lines(line, target_line).and(check_divisor(value)),
+        }
+    }
+
+    /// The line whose values the operation changes, `None` for swaps
+    fn changed_line(&self) -> Option<usize> {
+        match *self {
+            Self::Mul { line, .. } | Self::Div { line, .. } => Some(line),
+            Self::Swap { .. } | Self::SwapColumns { .. } => None,
+            Self::AddTo { target_line, .. }
+            | Self::SubTo { target_line, .. }
+            | Self::MulTo { target_line, .. }
+            | Self::DivTo { target_line, .. } => Some(target_line),
+        }
+    }
+
+    /// Changes the matrix without any checks
+    fn perform<M: LineStorage<T>>(self, matrix: &mut M) {
+        let scale = |matrix: &mut M, line: usize, f: &dyn Fn(T) -> T| {
+            let values = matrix
+                .values(line - 1)
+                .into_iter()
+                .map(f)
+                .collect::<Vec<_>>();
+            matrix.set_values(line - 1, &values);
+        };
+        let combine = |matrix: &mut M, line: usize, target_line: usize, f: &dyn Fn(T) -> T| {
+            let values = matrix
+                .values(target_line - 1)
+                .into_iter()
+                .zip(matrix.values(line - 1))
+                .map(|(target, value)| target + f(value))
+                .collect::<Vec<_>>();
+            matrix.set_values(target_line - 1, &values);
+        };
+        match self {
+            Self::Mul { line, value } => scale(matrix, line, &|x| x * value),
+            Self::Div { line, value } => scale(matrix, line, &|x| x / value),
+            Self::Swap { line, other_line } => matrix.exchange_lines(line - 1, other_line - 1),
+            Self::SwapColumns {
+                column,
+                other_column,
+            } => matrix.exchange_columns(column - 1, other_column - 1),
+            Self::AddTo { line, target_line } => combine(matrix, line, target_line, &|x| x),
+            Self::SubTo { line, target_line } => combine(matrix, line, target_line, &|x| -x),
+            Self::MulTo {
+                line,
+                target_line,
+                value,
+            } => combine(matrix, line, target_line, &|x| x * value),
+            Self::DivTo {
+                line,
+                target_line,
+                value,
+            } => combine(matrix, line, target_line, &|x| x / value),
+        }
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> LineStorage<T>
+    for AugmentedMatrix<ROWS, COLS, RESULTS, T>
+{
+    fn line_count(&self) -> usize {
+        ROWS
+    }
+
+    fn column_count(&self) -> usize {
+        COLS
+    }
+
+    fn entry(&self, line: usize, column: usize) -> T {
+        self.0[line].0[column]
+    }
+
+    fn values(&self, line: usize) -> Vec<T> {
+        self.0[line]
+            .0
+            .iter()
+            .chain(&self.0[line].1)
+            .copied()
+            .collect()
+    }
+
+    fn set_values(&mut self, line: usize, values: &[T]) {
+        let (coefficients, results) = values.split_at(COLS);
+        self.0[line].0.copy_from_slice(coefficients);
+        self.0[line].1.copy_from_slice(results);
+    }
+
+    fn exchange_lines(&mut self, line: usize, other_line: usize) {
+        self.0.swap(line, other_line);
+    }
+
+    fn exchange_columns(&mut self, column: usize, other_column: usize) {
+        for line in &mut self.0 {
+            line.0.swap(column, other_column);
+        }
+        if let Some(labels) = &mut self.1 {
+            labels.swap(column, other_column);
+        }
+    }
+}
+
+impl<T: Scalar> Display for RowOperation<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        let operand = |value: T| {
+            let text = value.format(precision);
+            if text.contains(['/', '+', '-']) {
+                format!("({text})")
+            } else {
+                text
+            }
+        };
+        let sign = |value: T| if value.is_negative() { '-' } else { '+' };
+        let abs = |value: T| if value.is_negative() { -value } else { value };
+        match *self {
+            Self::Mul { line, value } => {
+                f.write_fmt(format_args!("{} · {}", roman(line), operand(value)))
+            }
+            Self::Div { line, value } => {
+                f.write_fmt(format_args!("{} / {}", roman(line), operand(value)))
+            }
+            Self::Swap { line, other_line } => {
+                f.write_fmt(format_args!("{} <-> {}", roman(line), roman(other_line)))
+            }
+            Self::SwapColumns {
+                column,
+                other_column,
+            } => f.write_fmt(format_args!(
+                "x{} <-> x{}",
+                subscript(column),
+                subscript(other_column)
+            )),
+            Self::AddTo { line, target_line } => {
+                f.write_fmt(format_args!("{} + {}", roman(target_line), roman(line)))
+            }
+            Self::SubTo { line, target_line } => {
+                f.write_fmt(format_args!("{} - {}", roman(target_line), roman(line)))
+            }
+            Self::MulTo {
+                line,
+                target_line,
+                value,
+            } => f.write_fmt(format_args!(
+                "{} {} {}·{}",
+                roman(target_line),
+                sign(value),
+                operand(abs(value)),
+                roman(line)
+            )),
+            Self::DivTo {
+                line,
+                target_line,
+                value,
+            } => f.write_fmt(format_args!(
+                "{} {} {} / {}",
+                roman(target_line),
+                sign(value),
+                roman(line),
+                operand(abs(value))
+            )),
+        }
+    }
+}
+
+/// Writes a line number as a roman numeral, like in `II - 2·I`
+pub(crate) fn roman(mut number: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut result = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            result.push_str(numeral);
+            number -= value;
+        }
+    }
+    result
+}
+
+/// How the pivot of a column is chosen during elimination
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Pivoting {
+    /// The first non-zero entry, like it is done by hand
+    #[default]
+    None,
+    /// The entry with the largest magnitude in the pivot column
+    Partial,
+    /// The entry with the largest magnitude in the remaining matrix, swapping columns as well
+    Complete,
+    /// The entry with the largest magnitude relative to the largest coefficient of its line
+    ScaledPartial,
+}
+
+impl Display for Pivoting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::None => "no pivoting",
+            Self::Partial => "partial pivoting",
+            Self::Complete => "complete pivoting",
+            Self::ScaledPartial => "scaled partial pivoting",
+        })
+    }
+}
+
+/// A row operation together with the matrix it produced
+#[derive(Clone, Copy)]
+pub struct Step<const ROWS: usize, const COLS: usize, const RESULTS: usize = 1, T = f64> {
+    pub operation: RowOperation<T>,
+    pub matrix: AugmentedMatrix<ROWS, COLS, RESULTS, T>,
+    /// Set if the operation is a swap that was only made because of the pivoting strategy,
+    /// the previous pivot was not zero
+    pub pivoting: Option<Pivoting>,
+}
+
+/// The starting matrix of an elimination and every step applied to it
+#[derive(Clone)]
+pub struct Elimination<const ROWS: usize, const COLS: usize, const RESULTS: usize = 1, T = f64> {
+    pub start: AugmentedMatrix<ROWS, COLS, RESULTS, T>,
+    pub steps: Vec<Step<ROWS, COLS, RESULTS, T>>,
+    /// Every pivot candidate and divisor that is close to zero
+    pub warnings: Vec<SmallPivot>,
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    Elimination<ROWS, COLS, RESULTS, T>
+{
+    pub fn new(start: AugmentedMatrix<ROWS, COLS, RESULTS, T>) -> Self {
+        Self {
+            start,
+            steps: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// The matrix after the last step
+    pub fn result(&self) -> AugmentedMatrix<ROWS, COLS, RESULTS, T> {
+        self.steps.last().map_or(self.start, |step| step.matrix)
+    }
+
+    /// The applied row operations in order
+    pub fn operations(&self) -> impl Iterator<Item = RowOperation<T>> + '_ {
+        self.steps.iter().map(|step| step.operation)
+    }
+
+    /// Applies an operation to the current result and records it
+    pub fn push(&mut self, operation: RowOperation<T>) {
+        let matrix = operation.apply(self.result());
+        self.steps.push(Step {
+            operation,
+            matrix,
+            pivoting: None,
+        });
+    }
+
+    /// Applies a swap to the current result and records that `pivoting` chose it
+    pub fn push_pivoting(&mut self, operation: RowOperation<T>, pivoting: Pivoting) {
+        self.push(operation);
+        if let Some(step) = self.steps.last_mut() {
+            step.pivoting = Some(pivoting);
+        }
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    Step<ROWS, COLS, RESULTS, T>
+{
+    /// The operation as text, noting the pivoting strategy if it caused the step
+    fn label(&self, precision: usize) -> String {
+        match self.pivoting {
+            Some(pivoting) => format!("{:.*} ({pivoting})", precision, self.operation),
+            None => format!("{:.*}", precision, self.operation),
+        }
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Debug
+    for Step<ROWS, COLS, RESULTS, T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label(f.precision().unwrap_or(3)))?;
+        Display::fmt(&self.matrix, f)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Debug
+    for Elimination<ROWS, COLS, RESULTS, T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Display
+    for Elimination<ROWS, COLS, RESULTS, T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.start, f)?;
+        for step in &self.steps {
+            f.write_str("\n")?;
+            Debug::fmt(step, f)?;
+        }
+        for warning in &self.warnings {
+            f.write_fmt(format_args!("\n{warning}"))?;
+        }
+        Ok(())
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Drawable
+    for Elimination<ROWS, COLS, RESULTS, T>
+{
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        let scale = file.app_state.grid_size;
+        let (mut width, height) = self.start.draw(file, x, y, locked);
+        for step in &self.steps {
+            let text = step.label(3);
+            let text_width = text.chars().count() as i32 * 10;
+            file.elements.push(Element::draw_small_monospaced_text(
+                x + width + scale,
+                y + height / 2 - scale / 2,
+                locked,
+                text,
+            ));
+            width += text_width + scale * 2;
+            width += step.matrix.draw(file, x + width, y, locked).0;
+        }
+        let mut height = height;
+        for warning in &self.warnings {
+            file.elements.push(Element::draw_small_monospaced_text(
+                x,
+                y + height + scale,
+                locked,
+                warning.to_string(),
+            ));
+            height += scale * 2;
+        }
+        (width, height)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    AugmentedMatrix<ROWS, COLS, RESULTS, T>
+{
+    pub fn apply(self, operation: RowOperation<T>) -> Self {
+        operation.apply(self)
+    }
+
+    pub fn try_apply(self, operation: RowOperation<T>) -> Result<Self, RowOperationError> {
+        operation.try_apply(self)
+    }
+
+    /// Brings the matrix into row echelon form using Gaussian elimination
+    ///
+    /// A zero pivot is fixed by swapping with the first line below it that has a non-zero entry
+    /// in the pivot column
+    pub fn to_row_echelon(self) -> Elimination<ROWS, COLS, RESULTS, T> {
+        self.to_row_echelon_with(Pivoting::None)
+    }
+
+    /// Brings the matrix into row echelon form, choosing every pivot with `pivoting`
+    ///
+    /// [`Pivoting::Complete`] also swaps columns, so the columns of the result can belong to
+    /// different variables than before
+    pub fn to_row_echelon_with(self, pivoting: Pivoting) -> Elimination<ROWS, COLS, RESULTS, T> {
+        let mut elimination = Elimination::new(self);
+        forward(&mut elimination, pivoting);
+        elimination
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    AugmentedMatrix<ROWS, COLS, RESULTS, T>
+{
+    /// Brings the matrix into reduced row echelon form using Gauss-Jordan elimination
+    ///
+    /// After the forward elimination every pivot is scaled to one and the entries above it are
+    /// eliminated, starting with the last pivot
+    pub fn to_reduced_row_echelon(self) -> Elimination<ROWS, COLS, RESULTS, T> {
+        self.to_reduced_row_echelon_with(Pivoting::None)
+    }
+
+    /// Brings the matrix into reduced row echelon form, choosing every pivot with `pivoting`
+    ///
+    /// Column swaps of [`Pivoting::Complete`] are undone at the end and the result is reduced
+    /// once more without pivoting, so it is the same for every strategy
+    pub fn to_reduced_row_echelon_with(
+        self,
+        pivoting: Pivoting,
+    ) -> Elimination<ROWS, COLS, RESULTS, T> {
+        let mut elimination = Elimination::new(self);
+        reduce(&mut elimination, pivoting);
+        elimination
+    }
+}
+
+/// Where an elimination puts its steps, so every matrix type is eliminated the same way
+pub(crate) trait Record<T> {
+    type Matrix: LineStorage<T>;
+
+    /// The matrix after the last recorded step
+    fn current(&self) -> &Self::Matrix;
+
+    /// Applies an operation, `pivoting` is set if the strategy made an unnecessary swap
+    fn record(&mut self, operation: RowOperation<T>, pivoting: Option<Pivoting>);
+
+    fn warn(&mut self, warning: SmallPivot);
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Record<T>
+    for Elimination<ROWS, COLS, RESULTS, T>
+{
+    type Matrix = AugmentedMatrix<ROWS, COLS, RESULTS, T>;
+
+    fn current(&self) -> &Self::Matrix {
+        self.steps.last().map_or(&self.start, |step| &step.matrix)
+    }
+
+    fn record(&mut self, operation: RowOperation<T>, pivoting: Option<Pivoting>) {
+        match pivoting {
+            Some(pivoting) => self.push_pivoting(operation, pivoting),
+            None => self.push(operation),
+        }
+    }
+
+    fn warn(&mut self, warning: SmallPivot) {
+        self.warnings.push(warning);
+    }
+}
+
+/// Eliminates a matrix in place without keeping the steps
+pub(crate) struct Unrecorded<M>(pub M);
+
+impl<T: Scalar, M: LineStorage<T>> Record<T> for Unrecorded<M> {
+    type Matrix = M;
+
+    fn current(&self) -> &M {
+        &self.0
+    }
+
+    fn record(&mut self, operation: RowOperation<T>, _pivoting: Option<Pivoting>) {
+        operation.perform(&mut self.0);
+    }
+
+    fn warn(&mut self, _warning: SmallPivot) {}
+}
+
+/// Gaussian elimination, returning the column swaps made by [`Pivoting::Complete`]
+fn forward<T: Scalar, R: Record<T>>(record: &mut R, pivoting: Pivoting) -> Vec<RowOperation<T>> {
+    let (lines, columns) = (
+        record.current().line_count(),
+        record.current().column_count(),
+    );
+    let mut scales = (0..lines)
+        .map(|line| {
+            (0..columns)
+                .map(|col| record.current().entry(line, col).magnitude())
+                .fold(0.0, f64::max)
+        })
+        .collect::<Vec<_>>();
+    let tolerance = zero_tolerance(record.current());
+    let negligible = |value: T| value.is_negligible(tolerance);
+    let mut column_swaps = Vec::new();
+    let mut pivot_line = 1;
+    let mut col = 0;
+    while col < columns && pivot_line <= lines {
+        let current = record.current();
+        let candidates = match pivoting {
+            Pivoting::Complete => col..columns,
+            _ => col..col + 1,
+        };
+        let weight = |(line, col): (usize, usize)| {
+            let magnitude = current.entry(line - 1, col).magnitude();
+            match pivoting {
+                Pivoting::None => 0.0,
+                Pivoting::Partial | Pivoting::Complete => magnitude,
+                Pivoting::ScaledPartial => magnitude / scales[line - 1],
+            }
+        };
+        // On ties the earliest candidate wins, which avoids needless swaps
+        let best = |zero: &dyn Fn(T) -> bool| {
+            candidates
+                .clone()
+                .flat_map(|col| (pivot_line..=lines).map(move |line| (line, col)))
+                .filter(|&(line, col)| !zero(current.entry(line - 1, col)))
+                .fold(None, |best, candidate| match best {
+                    Some(best) if weight(best) >= weight(candidate) => Some(best),
+                    _ => Some(candidate),
+                })
+        };
+        // Only exact zeros are skipped here, so a pivot of 1e-17 is reported even though
+        // another line is used instead
+        let small = best(&|value| value == T::zero())
+            .map(|(line, col)| SmallPivot {
+                line,
+                column: col + 1,
+                magnitude: current.entry(line - 1, col).magnitude(),
+                tolerance,
+            })
+            .filter(|small| small.magnitude < tolerance);
+        let chosen = best(&negligible);
+        if let Some(small) = small {
+            record.warn(small);
+        }
+        let Some((line, pivot_col)) = chosen else {
+            if pivoting == Pivoting::Complete {
+                break;
+            }
+            col += 1;
+            continue;
+        };
+        if pivot_col != col {
+            let swap = RowOperation::SwapColumns {
+                column: col + 1,
+                other_column: pivot_col + 1,
+            };
+            record.record(swap, Some(pivoting));
+            column_swaps.push(swap);
+        }
+        if line != pivot_line {
+            let swap = RowOperation::Swap {
+                line: pivot_line,
+                other_line: line,
+            };
+            if negligible(record.current().entry(pivot_line - 1, col)) {
+                record.record(swap, None);
+            } else {
+                record.record(swap, Some(pivoting));
+            }
+            scales.swap(pivot_line - 1, line - 1);
+        }
+        let pivot = record.current().entry(pivot_line - 1, col);
+        for target_line in pivot_line + 1..=lines {
+            let value = record.current().entry(target_line - 1, col);
+            if negligible(value) {
+                continue;
+            }
+            record.record(eliminate(pivot_line, target_line, -value / pivot), None);
+        }
+        pivot_line += 1;
+        col += 1;
+    }
+    column_swaps
+}
+
+/// Gauss-Jordan elimination, the result is the same for every pivoting strategy
+pub(crate) fn reduce<T: Scalar, R: Record<T>>(record: &mut R, pivoting: Pivoting) {
+    let column_swaps = forward(record, pivoting);
+    backward(record);
+    if column_swaps.is_empty() {
+        return;
+    }
+    for operation in column_swaps.into_iter().rev() {
+        record.record(operation, None);
+    }
+    forward(record, Pivoting::None);
+    backward(record);
+}
+
+/// Scales every pivot of a matrix in row echelon form to one and eliminates the entries above
+/// it, starting with the last pivot
+fn backward<T: Scalar, R: Record<T>>(record: &mut R) {
+    let (lines, columns) = (
+        record.current().line_count(),
+        record.current().column_count(),
+    );
+    let tolerance = zero_tolerance(record.current());
+    let negligible = |value: T| value.is_negligible(tolerance);
+    for pivot_line in (1..=lines).rev() {
+        let current = record.current();
+        let Some(col) = (0..columns).find(|&col| !negligible(current.entry(pivot_line - 1, col)))
+        else {
+            continue;
+        };
+        let pivot = current.entry(pivot_line - 1, col);
+        if pivot != T::one() {
+            record.record(
+                RowOperation::Div {
+                    line: pivot_line,
+                    value: pivot,
+                },
+                None,
+            );
+        }
+        for target_line in 1..pivot_line {
+            let value = record.current().entry(target_line - 1, col);
+            if negligible(value) {
+                continue;
+            }
+            record.record(eliminate(pivot_line, target_line, -value), None);
+        }
+    }
+}
+
+/// Coefficients below this are treated as zero while eliminating `matrix`, the same bound
+/// relative to the largest coefficient that [`SmallPivot`] warnings use
+pub(crate) fn zero_tolerance<T: Scalar, M: LineStorage<T>>(matrix: &M) -> f64 {
+    let largest = (0..matrix.line_count())
+        .flat_map(|line| (0..matrix.column_count()).map(move |col| (line, col)))
+        .map(|(line, col)| matrix.entry(line, col).magnitude())
+        .fold(0.0, f64::max);
+    PIVOT_TOLERANCE * largest
+}
+
+/// The simplest operation adding `factor` times `line` to `target_line`
+pub(crate) fn eliminate<T: Scalar>(line: usize, target_line: usize, factor: T) -> RowOperation<T> {
+    if factor == T::one() {
+        RowOperation::AddTo { line, target_line }
+    } else if factor == -T::one() {
+        RowOperation::SubTo { line, target_line }
+    } else {
+        RowOperation::MulTo {
+            line,
+            target_line,
+            value: factor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::{rational::Rational, with_result::LineMatrix};
+
+    #[test]
+    fn row_echelon_records_the_operations() {
+        let elimination = LineMatrix::new([[2.0, 1.0], [4.0, 3.0]], [3.0, 7.0]).to_row_echelon();
+        assert_eq!(
+            elimination.operations().collect::<Vec<_>>(),
+            [RowOperation::MulTo {
+                line: 1,
+                target_line: 2,
+                value: -2.0
+            }]
+        );
+        let result = elimination.result();
+        assert_eq!(result[1].coefficients(), &[2.0, 1.0]);
+        assert_eq!(result[2].coefficients(), &[0.0, 1.0]);
+        assert_eq!(result[2].result(), 1.0);
+    }
+
+    #[test]
+    fn every_step_applies_its_operation_to_the_previous_matrix() {
+        let elimination = LineMatrix::new(
+            [[1.0, 2.0, 1.0], [2.0, 5.0, 3.0], [1.0, 4.0, 6.0]],
+            [4.0, 11.0, 17.0],
+        )
+        .to_row_echelon();
+        let mut matrix = elimination.start;
+        for step in &elimination.steps {
+            matrix = step.operation.apply(matrix);
+            for line in 1..=3 {
+                assert_eq!(
+                    matrix[line].coefficients(),
+                    step.matrix[line].coefficients()
+                );
+            }
+        }
+        let result = elimination.result();
+        assert_eq!(result[3].coefficients(), &[0.0, 0.0, 3.0]);
+        assert_eq!(result[3].result(), 7.0);
+    }
+
+    #[test]
+    fn zero_pivot_is_swapped_with_a_later_line() {
+        let elimination = LineMatrix::new([[0.0, 1.0], [1.0, 1.0]], [1.0, 2.0]).to_row_echelon();
+        assert_eq!(
+            elimination.operations().collect::<Vec<_>>(),
+            [RowOperation::Swap {
+                line: 1,
+                other_line: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn reduced_row_echelon_has_leading_ones_and_zeros_around_them() {
+        let result = LineMatrix::new(
+            [[1.0, 2.0, 1.0], [2.0, 5.0, 3.0], [1.0, 4.0, 6.0]],
+            [4.0, 11.0, 17.0],
+        )
+        .to_reduced_row_echelon()
+        .result();
+        for line in 1..=3 {
+            let expected: [f64; 3] = std::array::from_fn(|col| f64::from(col + 1 == line));
+            for (value, expected) in result[line].coefficients().iter().zip(expected) {
+                assert!((value - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn operations_are_written_like_by_hand() {
+        let cases = [
+            (
+                RowOperation::Mul {
+                    line: 1,
+                    value: 2.0,
+                },
+                "I · 2",
+            ),
+            (
+                RowOperation::Div {
+                    line: 2,
+                    value: 4.0,
+                },
+                "II / 4",
+            ),
+            (
+                RowOperation::AddTo {
+                    line: 1,
+                    target_line: 3,
+                },
+                "III + I",
+            ),
+            (
+                RowOperation::SubTo {
+                    line: 1,
+                    target_line: 2,
+                },
+                "II - I",
+            ),
+            (
+                RowOperation::MulTo {
+                    line: 1,
+                    target_line: 2,
+                    value: -2.0,
+                },
+                "II - 2·I",
+            ),
+            (
+                RowOperation::DivTo {
+                    line: 4,
+                    target_line: 9,
+                    value: 3.0,
+                },
+                "IX + IV / 3",
+            ),
+        ];
+        for (operation, text) in cases {
+            assert_eq!(operation.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn roman_numerals() {
+        assert_eq!(roman(1), "I");
+        assert_eq!(roman(4), "IV");
+        assert_eq!(roman(14), "XIV");
+        assert_eq!(roman(1994), "MCMXCIV");
+    }
+
+    #[test]
+    fn rectangular_row_echelon_ends_with_zero_lines() {
+        let result = AugmentedMatrix::new([[1.0, 1.0], [1.0, -1.0], [2.0, 0.0]], [3.0, 1.0, 4.0])
+            .to_row_echelon()
+            .result();
+        assert_eq!(result[2].coefficients(), &[0.0, -2.0]);
+        assert_eq!(result[3].coefficients(), &[0.0, 0.0]);
+        assert_eq!(result[3].result(), 0.0);
+    }
+
+    #[test]
+    fn rational_matrices_are_reduced_exactly() {
+        let result = AugmentedMatrix::<2, 2, 1, Rational>::from_integers([[3, 1], [1, 2]], [1, 0])
+            .to_reduced_row_echelon()
+            .result();
+        assert_eq!(result[1].result(), Rational::new(2, 5));
+        assert_eq!(result[2].result(), Rational::new(-1, 5));
+        assert_eq!(
+            format!("{result}"),
+            "\n┌╴        ╶┐\n│ 1 0│ 2/5 │\n│ 0 1│-1/5 │\n└╴        ╶┘"
+        );
+    }
+
+    #[test]
+    fn pivoting_strategies_choose_different_pivots() {
+        let matrix = LineMatrix::new(
+            [[1.0, 100.0, 0.0], [2.0, 1.0, 0.0], [3.0, 1000.0, 1.0]],
+            [0.0; 3],
+        );
+        let first = |pivoting| matrix.to_row_echelon_with(pivoting).steps[0];
+        assert!(matrix.to_row_echelon().steps[0].pivoting.is_none());
+        let step = first(Pivoting::Partial);
+        assert_eq!(
+            step.operation,
+            RowOperation::Swap {
+                line: 1,
+                other_line: 3
+            }
+        );
+        assert_eq!(step.pivoting, Some(Pivoting::Partial));
+        let step = first(Pivoting::ScaledPartial);
+        assert_eq!(
+            step.operation,
+            RowOperation::Swap {
+                line: 1,
+                other_line: 2
+            }
+        );
+        assert_eq!(step.pivoting, Some(Pivoting::ScaledPartial));
+        let step = first(Pivoting::Complete);
+        assert_eq!(
+            step.operation,
+            RowOperation::SwapColumns {
+                column: 1,
+                other_column: 2
+            }
+        );
+        assert!(format!("{step:?}").starts_with("x₁ <-> x₂ (complete pivoting)"));
+    }
+
+    #[test]
+    fn zero_pivot_swaps_are_not_blamed_on_the_strategy() {
+        let elimination = LineMatrix::new([[0.0, 1.0], [1.0, 1.0]], [1.0, 2.0])
+            .to_row_echelon_with(Pivoting::Partial);
+        assert_eq!(elimination.steps[0].pivoting, None);
+    }
+
+    #[test]
+    fn complete_pivoting_restores_the_columns() {
+        let matrix: LineMatrix<3> = LineMatrix::new(
+            [[1.0, 100.0, 0.0], [2.0, 1.0, 0.0], [3.0, 1000.0, 1.0]],
+            [1.0, 2.0, 3.0],
+        );
+        let expected = matrix.to_reduced_row_echelon().result();
+        let result = matrix
+            .to_reduced_row_echelon_with(Pivoting::Complete)
+            .result();
+        for line in 1..=3 {
+            for (value, expected) in result[line]
+                .0
+                .iter()
+                .chain(&result[line].1)
+                .zip(expected[line].0.iter().chain(&expected[line].1))
+            {
+                assert!((value - expected).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,535 @@
+//! Reading and writing systems of linear equations like `2x + 3y - z = 5`, one equation per
+//! line
+//!
+//! Both sides can contain variables and numbers. Coefficients can be left out (`x`, `-y`) or
+//! written as integers, decimals or fractions (`0.5x`, `1/2 x`, `x/2`, `3·z`). Variables are a
+//! letter with an optional index like `x`, `x2`, `x₂` or `x_2`
+
+use super::{
+    dynamic::DynLineMatrix,
+    error::{ParseEquationError, ParseEquationErrorKind, SizeMismatch},
+    variable::{digit, Variable},
+    AugmentedMatrix,
+};
+use crate::{
+    exporters::excalidraw::{Drawable, ExcalidrawFile},
+    matrix::{draw_text_lines, scalar::Scalar},
+};
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+/// A system of linear equations with the variables it was written in
+#[derive(Clone, PartialEq)]
+pub struct EquationSystem<T = f64> {
+    /// Sorted by letter and then by index, so `x₂` comes before `x₁₀`
+    pub variables: Vec<Variable>,
+    /// One line per equation, one coefficient per variable
+    pub coefficients: Vec<Vec<T>>,
+    pub results: Vec<T>,
+}
+
+impl<T: Scalar + FromStr> FromStr for EquationSystem<T> {
+    type Err = ParseEquationError;
+
+    /// Empty lines are skipped
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut equations = Vec::new();
+        for (i, text) in s.lines().enumerate() {
+            if text.trim().is_empty() {
+                continue;
+            }
+            let error = |position, kind| ParseEquationError {
+                input: text.to_string(),
+                equation: i + 1,
+                position,
+                kind,
+            };
+            let chars = text.chars().collect::<Vec<_>>();
+            let equals = chars
+                .iter()
+                .enumerate()
+                .filter(|&(_, &c)| c == '=')
+                .map(|(position, _)| position)
+                .collect::<Vec<_>>();
+            let equals = match equals.as_slice() {
+                [] => return Err(error(chars.len(), ParseEquationErrorKind::MissingEquals)),
+                [equals] => *equals,
+                [_, second, ..] => {
+                    return Err(error(*second, ParseEquationErrorKind::SeveralEquals))
+                }
+            };
+            let mut terms = side::<T>(&chars, 0, equals).map_err(|(p, kind)| error(p, kind))?;
+            let right =
+                side::<T>(&chars, equals + 1, chars.len()).map_err(|(p, kind)| error(p, kind))?;
+            terms.extend(
+                right
+                    .into_iter()
+                    .map(|(variable, value)| (variable, -value)),
+            );
+            equations.push(terms);
+        }
+        if equations.is_empty() {
+            return Err(ParseEquationError {
+                input: String::new(),
+                equation: 1,
+                position: 0,
+                kind: ParseEquationErrorKind::Empty,
+            });
+        }
+        let mut variables = equations
+            .iter()
+            .flatten()
+            .filter_map(|&(variable, _)| variable)
+            .collect::<Vec<_>>();
+        variables.sort();
+        variables.dedup();
+        let mut coefficients = vec![vec![T::zero(); variables.len()]; equations.len()];
+        let mut results = vec![T::zero(); equations.len()];
+        for (line, terms) in equations.into_iter().enumerate() {
+            for (variable, value) in terms {
+                match variable {
+                    Some(variable) => {
+                        let col = variables.iter().position(|v| *v == variable).unwrap();
+                        coefficients[line][col] += value;
+                    }
+                    // Numbers are moved to the right side
+                    None => results[line] -= value,
+                }
+            }
+        }
+        Ok(Self {
+            variables,
+            coefficients,
+            results,
+        })
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> TryFrom<EquationSystem<T>>
+    for AugmentedMatrix<ROWS, COLS, 1, T>
+{
+    type Error = SizeMismatch;
+
+    /// Fails unless there are `ROWS` equations in `COLS` variables, the columns are labelled
+    /// with the variables
+    fn try_from(system: EquationSystem<T>) -> Result<Self, Self::Error> {
+        if system.results.len() != ROWS {
+            return Err(SizeMismatch::new(ROWS, system.results.len()));
+        }
+        if system.variables.len() != COLS {
+            return Err(SizeMismatch::new(COLS, system.variables.len()));
+        }
+        Ok(Self::new(
+            std::array::from_fn(|line| std::array::from_fn(|col| system.coefficients[line][col])),
+            std::array::from_fn(|line| system.results[line]),
+        )
+        .with_labels(std::array::from_fn(|col| system.variables[col])))
+    }
+}
+
+impl<T: Scalar> TryFrom<EquationSystem<T>> for DynLineMatrix<T> {
+    type Error = SizeMismatch;
+
+    /// Fails unless there are as many equations as variables, the columns are labelled with the
+    /// variables
+    fn try_from(system: EquationSystem<T>) -> Result<Self, Self::Error> {
+        Ok(Self::try_new(system.coefficients, system.results)?.with_labels(system.variables))
+    }
+}
+
+/// A matrix written as a system of equations, see [`AugmentedMatrix::equations`]
+#[derive(Clone, Copy)]
+pub struct Equations<const ROWS: usize, const COLS: usize, T = f64>(
+    AugmentedMatrix<ROWS, COLS, 1, T>,
+);
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> AugmentedMatrix<ROWS, COLS, 1, T> {
+    /// The matrix as a system of equations like `2x + 3y − z = 5`
+    ///
+    /// The variables are the labels of the matrix or [`Variable::defaults`]. Terms that round
+    /// to zero are left out and so are coefficients of one
+    pub fn equations(&self) -> Equations<ROWS, COLS, T> {
+        Equations(*self)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> Equations<ROWS, COLS, T> {
+    fn lines(&self, precision: usize) -> Vec<String> {
+        let matrix = &self.0;
+        let labels = matrix.labels().copied().unwrap_or_else(Variable::defaults);
+        matrix
+            .0
+            .iter()
+            .map(|line| {
+                let mut text = String::new();
+                for (&value, label) in line.0.iter().zip(labels) {
+                    let (negative, coefficient) = split_sign(value, precision);
+                    if coefficient == "0" {
+                        continue;
+                    }
+                    let sign = match (text.is_empty(), negative) {
+                        (true, false) => "",
+                        (true, true) => "−",
+                        (false, false) => " + ",
+                        (false, true) => " − ",
+                    };
+                    text.push_str(sign);
+                    if coefficient == "1" {
+                        text.push_str(&label.to_string());
+                    } else if coefficient.contains(['+', '-']) {
+                        text.push_str(&format!("({coefficient}){label}"));
+                    } else if coefficient.contains('/') {
+                        text.push_str(&format!("{coefficient} {label}"));
+                    } else {
+                        text.push_str(&format!("{coefficient}{label}"));
+                    }
+                }
+                if text.is_empty() {
+                    text.push('0');
+                }
+                let result = match split_sign(line.result(), precision) {
+                    (true, result) if result != "0" => format!("−{result}"),
+                    (_, result) => result,
+                };
+                format!("{text} = {result}")
+            })
+            .collect()
+    }
+}
+
+/// Whether the value reads as negative, together with the formatted value without that sign
+///
+/// Also catches values like `-2i` that are not negative but read like it
+fn split_sign<T: Scalar>(value: T, precision: usize) -> (bool, String) {
+    let text = value.format(precision);
+    let negative = value.is_negative() || text.starts_with('-') && !text[1..].contains(['+', '-']);
+    let magnitude = if negative { -value } else { value };
+    (negative, magnitude.format(precision))
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> Debug for Equations<ROWS, COLS, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> Display for Equations<ROWS, COLS, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in self.lines(f.precision().unwrap_or(3)) {
+            f.write_fmt(format_args!("\n{line}"))?;
+        }
+        Ok(())
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> Drawable for Equations<ROWS, COLS, T> {
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        draw_text_lines(file, x, y, locked, self.lines(3))
+    }
+}
+
+/// A variable with its coefficient, numbers without a variable have the variable `None`
+type Term<T> = (Option<Variable>, T);
+
+/// The terms of the side `start..end` of an equation with the sign they are written with
+fn side<T: Scalar + FromStr>(
+    chars: &[char],
+    start: usize,
+    end: usize,
+) -> Result<Vec<Term<T>>, (usize, ParseEquationErrorKind)> {
+    let mut terms = Vec::new();
+    let mut i = start;
+    let skip_whitespace = |i: &mut usize| {
+        while *i < end && chars[*i].is_whitespace() {
+            *i += 1;
+        }
+    };
+    loop {
+        skip_whitespace(&mut i);
+        if i == end {
+            if terms.is_empty() {
+                return Err((i, ParseEquationErrorKind::ExpectedTerm));
+            }
+            return Ok(terms);
+        }
+        let negative = match chars[i] {
+            '+' | '-' | '−' => {
+                i += 1;
+                chars[i - 1] != '+'
+            }
+            _ if terms.is_empty() => false,
+            c => return Err((i, ParseEquationErrorKind::UnexpectedCharacter(c))),
+        };
+        skip_whitespace(&mut i);
+        let mut value = number::<T>(chars, &mut i, end)?;
+        skip_whitespace(&mut i);
+        if value.is_some() && i < end && chars[i] == '/' {
+            let denominator = denominator::<T>(chars, &mut i, end)?;
+            value = value.map(|value| value / denominator);
+            skip_whitespace(&mut i);
+        }
+        let times = value.is_some() && i < end && matches!(chars[i], '·' | '⋅' | '*' | '×');
+        if times {
+            i += 1;
+            skip_whitespace(&mut i);
+        }
+        let variable = variable(chars, &mut i, end)?;
+        if variable.is_none() && (value.is_none() || times) {
+            return Err((i, ParseEquationErrorKind::ExpectedTerm));
+        }
+        let mut value = value.unwrap_or(T::one());
+        skip_whitespace(&mut i);
+        // A trailing fraction like in `x/2`
+        if variable.is_some() && i < end && chars[i] == '/' {
+            value /= denominator::<T>(chars, &mut i, end)?;
+        }
+        terms.push((variable, if negative { -value } else { value }));
+    }
+}
+
+/// Reads an unsigned integer or decimal, `None` if there is no digit at `i`
+fn number<T: FromStr>(
+    chars: &[char],
+    i: &mut usize,
+    end: usize,
+) -> Result<Option<T>, (usize, ParseEquationErrorKind)> {
+    let start = *i;
+    while *i < end && (chars[*i].is_ascii_digit() || chars[*i] == '.') {
+        *i += 1;
+    }
+    if *i == start {
+        return Ok(None);
+    }
+    let text = chars[start..*i].iter().collect::<String>();
+    match text.parse() {
+        Ok(value) => Ok(Some(value)),
+        Err(_) => Err((start, ParseEquationErrorKind::InvalidNumber(text))),
+    }
+}
+
+/// Reads `/` followed by a number that is not zero
+fn denominator<T: Scalar + FromStr>(
+    chars: &[char],
+    i: &mut usize,
+    end: usize,
+) -> Result<T, (usize, ParseEquationErrorKind)> {
+    *i += 1;
+    while *i < end && chars[*i].is_whitespace() {
+        *i += 1;
+    }
+    let start = *i;
+    let denominator =
+        number::<T>(chars, i, end)?.ok_or((*i, ParseEquationErrorKind::ExpectedNumber))?;
+    if denominator.is_zero() {
+        let text = chars[start..*i].iter().collect();
+        return Err((start, ParseEquationErrorKind::InvalidNumber(text)));
+    }
+    Ok(denominator)
+}
+
+/// Reads a letter followed by an optional index, `None` if there is no letter at `i`
+fn variable(
+    chars: &[char],
+    i: &mut usize,
+    end: usize,
+) -> Result<Option<Variable>, (usize, ParseEquationErrorKind)> {
+    if *i == end || !chars[*i].is_alphabetic() {
+        return Ok(None);
+    }
+    let start = *i;
+    let letter = chars[*i];
+    *i += 1;
+    if *i + 1 < end && chars[*i] == '_' && digit(chars[*i + 1]).is_some() {
+        *i += 1;
+    }
+    let mut index = None;
+    let mut overflow = false;
+    while let Some(digit) = chars.get(*i).filter(|_| *i < end).and_then(|&c| digit(c)) {
+        let next = index
+            .unwrap_or(0usize)
+            .checked_mul(10)
+            .and_then(|index| index.checked_add(digit));
+        overflow |= next.is_none();
+        index = next.or(index);
+        *i += 1;
+    }
+    if overflow {
+        let text = chars[start..*i].iter().collect();
+        return Err((start, ParseEquationErrorKind::InvalidVariable(text)));
+    }
+    Ok(Some(Variable { letter, index }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::{complex::Complex, rational::Rational, with_result::LineMatrix};
+
+    fn parse(text: &str) -> EquationSystem {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn reads_systems_of_equations() {
+        let system = parse("2x + 3y - z = 5\n\nx - y = 1\n y/2 + 1/2 z = 0");
+        assert_eq!(
+            system.variables,
+            [Variable::new('x'), Variable::new('y'), Variable::new('z')]
+        );
+        assert_eq!(
+            system.coefficients,
+            [[2.0, 3.0, -1.0], [1.0, -1.0, 0.0], [0.0, 0.5, 0.5]]
+        );
+        assert_eq!(system.results, [5.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn reads_every_way_to_write_a_term() {
+        let cases = [
+            ("x = 1", 1.0),
+            ("-x = 1", -1.0),
+            ("−x = 1", -1.0),
+            ("0.5x = 1", 0.5),
+            ("3·x = 1", 3.0),
+            ("3 * x = 1", 3.0),
+            ("1/4 x = 1", 0.25),
+            ("x/4 = 1", 0.25),
+            ("- x / 4 = 1", -0.25),
+            ("3/2 x/3 = 1", 0.5),
+        ];
+        for (text, coefficient) in cases {
+            assert_eq!(parse(text).coefficients, [[coefficient]], "{text}");
+        }
+    }
+
+    #[test]
+    fn moves_variables_left_and_numbers_right() {
+        let system = parse("x + 1 = 2y - 3 + x/2");
+        assert_eq!(system.coefficients, [[0.5, -2.0]]);
+        assert_eq!(system.results, [-4.0]);
+    }
+
+    #[test]
+    fn sorts_indexed_variables_by_their_value() {
+        let system = parse("x₁₀ + x2 + x_1 = 1");
+        assert_eq!(
+            system.variables,
+            [1, 2, 10].map(|index| Variable::indexed('x', index))
+        );
+    }
+
+    #[test]
+    fn reads_exact_fractions() {
+        let system: EquationSystem<Rational> = "x/3 + 2/3 y = 1".parse().unwrap();
+        assert_eq!(
+            system.coefficients,
+            [[Rational::new(1, 3), Rational::new(2, 3)]]
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        use ParseEquationErrorKind::*;
+        let cases = [
+            ("\n  \n", 1, 0, Empty),
+            ("x + y", 1, 5, MissingEquals),
+            ("x = y = 1", 1, 6, SeveralEquals),
+            ("x + = 1", 1, 4, ExpectedTerm),
+            ("2· = 1", 1, 3, ExpectedTerm),
+            ("x = 1\ny = ", 2, 4, ExpectedTerm),
+            ("x/ = 1", 1, 3, ExpectedNumber),
+            ("x/0 = 1", 1, 2, InvalidNumber(String::from("0"))),
+            ("1/0 x = 1", 1, 2, InvalidNumber(String::from("0"))),
+            ("1.2.3x = 1", 1, 0, InvalidNumber(String::from("1.2.3"))),
+            ("x ? 1 = 2", 1, 2, UnexpectedCharacter('?')),
+            (
+                "2y + x99999999999999999999999 = 1",
+                1,
+                5,
+                InvalidVariable(String::from("x99999999999999999999999")),
+            ),
+        ];
+        for (text, equation, position, kind) in cases {
+            let Err(error) = text.parse::<EquationSystem>() else {
+                panic!("{text} should not be read");
+            };
+            assert_eq!(
+                (error.equation, error.position, &error.kind),
+                (equation, position, &kind),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn converts_to_labelled_matrices() {
+        let system = parse("a + b = 3\na - b = 1");
+        let labels = vec![Variable::new('a'), Variable::new('b')];
+        let dynamic = DynLineMatrix::try_from(system.clone()).unwrap();
+        assert_eq!(dynamic.labels(), Some(labels.as_slice()));
+        assert_eq!(dynamic[2].coefficients(), &[1.0, -1.0]);
+        let fixed = LineMatrix::<2>::try_from(system.clone()).unwrap();
+        assert_eq!(fixed.labels().map(|labels| labels.to_vec()), Some(labels));
+        assert_eq!(DynLineMatrix::from(fixed), dynamic);
+        assert!(LineMatrix::<3>::try_from(system).is_err());
+        assert!(DynLineMatrix::try_from(parse("x + y = 1")).is_err());
+    }
+
+    #[test]
+    fn writes_matrices_as_equations() {
+        let matrix = LineMatrix::new(
+            [[2.0, 3.0, -1.0], [-1.0, 0.0, 1.0], [0.0, -2.5, 0.0]],
+            [5.0, -1.0, 0.0],
+        );
+        assert_eq!(
+            matrix.equations().to_string(),
+            "\n2x + 3y − z = 5\n−x + z = −1\n−2.5y = 0"
+        );
+    }
+
+    #[test]
+    fn leaves_out_terms_that_round_to_zero() {
+        let matrix = LineMatrix::new([[1.0, 1e-5], [0.0, 0.0]], [1.0, 2.0]);
+        assert_eq!(matrix.equations().to_string(), "\nx = 1\n0 = 2");
+        assert_eq!(
+            format!("{:.5}", matrix.equations()),
+            "\nx + 0.00001y = 1\n0 = 2"
+        );
+    }
+
+    #[test]
+    fn uses_the_labels_and_keeps_fractions_readable() {
+        let half = Rational::new(1, 2);
+        let matrix = LineMatrix::new(
+            [[half, -Rational::from(1)], [Rational::from(0), -half]],
+            [Rational::from(1), Rational::from(0)],
+        )
+        .with_labels([Variable::new('a'), Variable::indexed('b', 2)]);
+        assert_eq!(
+            matrix.equations().to_string(),
+            "\n1/2 a − b₂ = 1\n−1/2 b₂ = 0"
+        );
+    }
+
+    #[test]
+    fn written_equations_are_read_back() {
+        let matrix = LineMatrix::new([[2.0, -3.0], [1.0, 0.5]], [4.0, 1.0]).with_default_labels();
+        let system: EquationSystem = matrix.equations().to_string().parse().unwrap();
+        assert_eq!(system.coefficients, [[2.0, -3.0], [1.0, 0.5]]);
+        assert_eq!(system.results, [4.0, 1.0]);
+    }
+
+    #[test]
+    fn negative_results_use_the_same_minus_sign() {
+        let matrix = LineMatrix::new([[0.0, 0.0], [-1.0, 2.0]], [-2.0, -1e-5]);
+        assert_eq!(matrix.equations().to_string(), "\n0 = −2\n−x + 2y = 0");
+        let complex = LineMatrix::new([[Complex::ONE]], [Complex::new(1.0, -2.0)]);
+        assert_eq!(complex.equations().to_string(), "\nx = 1-2i");
+        let negative = LineMatrix::new([[Complex::ONE]], [Complex::new(0.0, -2.0)]);
+        assert_eq!(negative.equations().to_string(), "\nx = −2i");
+        let system: EquationSystem = matrix.equations().to_string().parse().unwrap();
+        assert_eq!(system.results, [-2.0, 0.0]);
+    }
+}
@@ -0,0 +1,240 @@
+use super::elimination::roman;
+use std::{error::Error, fmt::Display};
+
+/// The reason a row operation could not be applied
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowOperationError {
+    /// The line is out of range, **lines are 1-based**
+    InvalidLine(usize),
+    /// The column is out of range, **columns are 1-based**
+    InvalidColumn(usize),
+    DivisionByZero,
+    /// The line would be combined or swapped with itself
+    SameLine(usize),
+    /// The operation would produce a NaN or infinite value
+    NonFinite,
+}
+
+impl Display for RowOperationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLine(line) => f.write_fmt(format_args!("Invalid line {line}")),
+            Self::InvalidColumn(column) => f.write_fmt(format_args!("Invalid column {column}")),
+            Self::DivisionByZero => f.write_str("Division by zero"),
+            Self::SameLine(line) => {
+                f.write_fmt(format_args!("Line {line} cannot be both source and target"))
+            }
+            Self::NonFinite => f.write_str("Operation produced a non-finite value"),
+        }
+    }
+}
+
+impl Error for RowOperationError {}
+
+/// A matrix did not have the expected number of lines or columns
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeMismatch {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl SizeMismatch {
+    pub fn new(expected: usize, found: usize) -> Self {
+        Self { expected, found }
+    }
+}
+
+impl Display for SizeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "Expected size {}, found {}",
+            self.expected, self.found
+        ))
+    }
+}
+
+impl Error for SizeMismatch {}
+
+/// The matrix has no inverse, elimination left no non-zero pivot in `column`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SingularMatrix {
+    /// The column without a pivot, **1-based**
+    pub column: usize,
+}
+
+impl Display for SingularMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "Matrix is singular, column {} has no non-zero pivot",
+            self.column
+        ))
+    }
+}
+
+impl Error for SingularMatrix {}
+
+/// The QR iteration did not split off an eigenvalue within `iterations` iterations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoConvergence {
+    pub iterations: usize,
+}
+
+impl Display for NoConvergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "The QR iteration did not converge within {} iterations",
+            self.iterations
+        ))
+    }
+}
+
+impl Error for NoConvergence {}
+
+/// A column is a linear combination of the columns before it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinearlyDependent {
+    /// The dependent column, **1-based**
+    pub column: usize,
+}
+
+impl Display for LinearlyDependent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "Column {} depends linearly on the columns before it",
+            self.column
+        ))
+    }
+}
+
+impl Error for LinearlyDependent {}
+
+/// Text could not be read as a row operation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseOperationError {
+    pub input: String,
+    /// The character the problem was found at, **0-based**
+    pub position: usize,
+    pub kind: ParseOperationErrorKind,
+}
+
+/// What was wrong with the text of a row operation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseOperationErrorKind {
+    Empty,
+    ExpectedLine,
+    ExpectedColumn,
+    ExpectedNumber,
+    ExpectedEnd,
+    InvalidLine(String),
+    InvalidNumber(String),
+    UnexpectedCharacter(char),
+    UnclosedParenthesis,
+    /// The assigned line does not appear unscaled on the right side
+    MissingTarget(usize),
+    /// The text describes more than one row operation, like scaling a line and adding another
+    /// one to it
+    NotASingleOperation,
+}
+
+impl Display for ParseOperationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ParseOperationErrorKind::Empty => f.write_str("Expected a row operation"),
+            ParseOperationErrorKind::ExpectedLine => {
+                f.write_str("Expected a line like `II` or `R2`")
+            }
+            ParseOperationErrorKind::ExpectedColumn => {
+                f.write_str("Expected a column like `x₁` or `x1`")
+            }
+            ParseOperationErrorKind::ExpectedNumber => f.write_str("Expected a number"),
+            ParseOperationErrorKind::ExpectedEnd => {
+                f.write_str("Expected the end of the operation")
+            }
+            ParseOperationErrorKind::InvalidLine(line) => {
+                f.write_fmt(format_args!("Invalid line `{line}`"))
+            }
+            ParseOperationErrorKind::InvalidNumber(number) => {
+                f.write_fmt(format_args!("Invalid number `{number}`"))
+            }
+            ParseOperationErrorKind::UnexpectedCharacter(c) => {
+                f.write_fmt(format_args!("Unexpected `{c}`"))
+            }
+            ParseOperationErrorKind::UnclosedParenthesis => f.write_str("Unclosed parenthesis"),
+            ParseOperationErrorKind::MissingTarget(line) => f.write_fmt(format_args!(
+                "Line {} has to appear on the right side without a factor",
+                roman(*line)
+            )),
+            ParseOperationErrorKind::NotASingleOperation => {
+                f.write_str("Not a single row operation, split it into several steps")
+            }
+        }?;
+        f.write_fmt(format_args!(
+            " at position {}\n{}\n{}^",
+            self.position + 1,
+            self.input,
+            " ".repeat(self.position)
+        ))
+    }
+}
+
+impl Error for ParseOperationError {}
+
+/// A line of text could not be read as a linear equation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseEquationError {
+    /// The text of the equation
+    pub input: String,
+    /// The line of the equation, **1-based**
+    pub equation: usize,
+    /// The character the problem was found at, **0-based**
+    pub position: usize,
+    pub kind: ParseEquationErrorKind,
+}
+
+/// What was wrong with the text of an equation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseEquationErrorKind {
+    /// The text does not contain any equation
+    Empty,
+    MissingEquals,
+    SeveralEquals,
+    /// A side is empty or a sign is not followed by a number or variable
+    ExpectedTerm,
+    ExpectedNumber,
+    InvalidNumber(String),
+    /// The index of the variable is too large
+    InvalidVariable(String),
+    UnexpectedCharacter(char),
+}
+
+impl Display for ParseEquationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ParseEquationErrorKind::Empty => return f.write_str("Expected at least one equation"),
+            ParseEquationErrorKind::MissingEquals => f.write_str("Expected `=`"),
+            ParseEquationErrorKind::SeveralEquals => f.write_str("Expected only one `=`"),
+            ParseEquationErrorKind::ExpectedTerm => {
+                f.write_str("Expected a term like `3x`, `-y` or `5`")
+            }
+            ParseEquationErrorKind::ExpectedNumber => f.write_str("Expected a number"),
+            ParseEquationErrorKind::InvalidNumber(number) => {
+                f.write_fmt(format_args!("Invalid number `{number}`"))
+            }
+            ParseEquationErrorKind::InvalidVariable(variable) => {
+                f.write_fmt(format_args!("Invalid variable `{variable}`"))
+            }
+            ParseEquationErrorKind::UnexpectedCharacter(c) => {
+                f.write_fmt(format_args!("Unexpected `{c}`"))
+            }
+        }?;
+        f.write_fmt(format_args!(
+            " in equation {} at position {}\n{}\n{}^",
+            self.equation,
+            self.position + 1,
+            self.input,
+            " ".repeat(self.position)
+        ))
+    }
+}
+
+impl Error for ParseEquationError {}
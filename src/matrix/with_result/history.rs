@@ -0,0 +1,316 @@
+use super::{
+    condition::SmallPivot,
+    elimination::{zero_tolerance, Elimination, RowOperation, Step},
+    error::RowOperationError,
+    AugmentedMatrix,
+};
+use crate::{
+    exporters::excalidraw::{Drawable, ExcalidrawFile},
+    matrix::scalar::Scalar,
+};
+use std::fmt::{Debug, Display};
+
+/// Row operations applied to a matrix one after another, which can be undone and redone
+#[derive(Clone)]
+pub struct History<const ROWS: usize, const COLS: usize, const RESULTS: usize = 1, T = f64> {
+    elimination: Elimination<ROWS, COLS, RESULTS, T>,
+    /// Undone steps, the most recently undone one last
+    undone: Vec<Step<ROWS, COLS, RESULTS, T>>,
+}
+
+/// An operation of a [`History`] together with the matrix before and after it
+#[derive(Clone, Copy)]
+pub struct Record<const ROWS: usize, const COLS: usize, const RESULTS: usize = 1, T = f64> {
+    pub before: AugmentedMatrix<ROWS, COLS, RESULTS, T>,
+    pub operation: RowOperation<T>,
+    pub after: AugmentedMatrix<ROWS, COLS, RESULTS, T>,
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    History<ROWS, COLS, RESULTS, T>
+{
+    pub fn new(start: AugmentedMatrix<ROWS, COLS, RESULTS, T>) -> Self {
+        Self {
+            elimination: Elimination::new(start),
+            undone: Vec::new(),
+        }
+    }
+
+    /// The matrix after the last operation that was not undone
+    pub fn current(&self) -> AugmentedMatrix<ROWS, COLS, RESULTS, T> {
+        self.elimination.result()
+    }
+
+    /// The operations that were not undone as an [`Elimination`]
+    pub fn elimination(&self) -> &Elimination<ROWS, COLS, RESULTS, T> {
+        &self.elimination
+    }
+
+    /// Every operation that was not undone with the matrix before and after it
+    pub fn records(&self) -> impl Iterator<Item = Record<ROWS, COLS, RESULTS, T>> + '_ {
+        let befores = std::iter::once(self.elimination.start)
+            .chain(self.elimination.steps.iter().map(|step| step.matrix));
+        befores
+            .zip(&self.elimination.steps)
+            .map(|(before, step)| Record {
+                before,
+                operation: step.operation,
+                after: step.matrix,
+            })
+    }
+
+    /// Applies an operation to the current matrix, forgetting every undone operation
+    ///
+    /// Panics with the error [`Self::try_apply`] would return, so every recorded operation can
+    /// be replayed
+    pub fn apply(&mut self, operation: RowOperation<T>) -> &mut Self {
+        if let Err(error) = self.try_apply(operation) {
+            panic!("{error}");
+        }
+        self
+    }
+
+    /// Applies an operation to the current matrix, leaving the history untouched if it fails
+    pub fn try_apply(
+        &mut self,
+        operation: RowOperation<T>,
+    ) -> Result<&mut Self, RowOperationError> {
+        push_checked(&mut self.elimination, operation)?;
+        self.undone.clear();
+        Ok(self)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.elimination.steps.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Reverts the last operation and returns it, `None` if there is nothing to undo
+    pub fn undo(&mut self) -> Option<RowOperation<T>> {
+        let step = self.elimination.steps.pop()?;
+        let operation = step.operation;
+        self.undone.push(step);
+        Some(operation)
+    }
+
+    /// Applies the last undone operation again and returns it, `None` if there is nothing to
+    /// redo
+    pub fn redo(&mut self) -> Option<RowOperation<T>> {
+        let step = self.undone.pop()?;
+        let operation = step.operation;
+        self.elimination.steps.push(step);
+        Some(operation)
+    }
+
+    /// Applies the operations that were not undone to a different matrix
+    ///
+    /// The matrix may have another shape, replaying fails if an operation refers to a line or
+    /// column it does not have
+    pub fn replay<const OTHER_ROWS: usize, const OTHER_COLS: usize, const OTHER_RESULTS: usize>(
+        &self,
+        start: AugmentedMatrix<OTHER_ROWS, OTHER_COLS, OTHER_RESULTS, T>,
+    ) -> Result<Elimination<OTHER_ROWS, OTHER_COLS, OTHER_RESULTS, T>, RowOperationError> {
+        let mut elimination = Elimination::new(start);
+        for operation in self.elimination.operations() {
+            push_checked(&mut elimination, operation)?;
+        }
+        Ok(elimination)
+    }
+}
+
+/// Applies an operation with [`RowOperation::try_apply`] and records it, the one check shared
+/// by applying and replaying
+///
+/// Divisors close to zero are accepted and reported as a [`SmallPivot`] of the divided line,
+/// in the column of the first entry equal to the divisor
+fn push_checked<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>(
+    elimination: &mut Elimination<ROWS, COLS, RESULTS, T>,
+    operation: RowOperation<T>,
+) -> Result<(), RowOperationError> {
+    let before = elimination.result();
+    let matrix = operation.try_apply(before)?;
+    if let RowOperation::Div { line, value } | RowOperation::DivTo { line, value, .. } = operation {
+        let tolerance = zero_tolerance(&before);
+        if value.is_negligible(tolerance) {
+            let column = before[line].0.iter().position(|&entry| entry == value);
+            elimination.warnings.push(SmallPivot {
+                line,
+                column: column.map_or(1, |col| col + 1),
+                magnitude: value.magnitude(),
+                tolerance,
+            });
+        }
+    }
+    elimination.steps.push(Step {
+        operation,
+        matrix,
+        pivoting: None,
+    });
+    Ok(())
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    AugmentedMatrix<ROWS, COLS, RESULTS, T>
+{
+    /// Starts recording the row operations applied to the matrix
+    pub fn history(self) -> History<ROWS, COLS, RESULTS, T> {
+        History::new(self)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Debug
+    for History<ROWS, COLS, RESULTS, T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Display
+    for History<ROWS, COLS, RESULTS, T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.elimination, f)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar> Drawable
+    for History<ROWS, COLS, RESULTS, T>
+{
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        self.elimination.draw(file, x, y, locked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::with_result::LineMatrix;
+
+    fn matrix() -> LineMatrix<2> {
+        LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0])
+    }
+
+    const SWAP: RowOperation = RowOperation::Swap {
+        line: 1,
+        other_line: 2,
+    };
+
+    const SCALE: RowOperation = RowOperation::Mul {
+        line: 1,
+        value: 2.0,
+    };
+
+    #[test]
+    fn undo_and_redo_move_between_the_steps() {
+        let mut history = matrix().history();
+        history.apply(SWAP).apply(SCALE);
+        assert_eq!(history.current()[1].coefficients(), &[6.0, 8.0]);
+        assert_eq!(history.undo(), Some(SCALE));
+        assert_eq!(history.current()[1].coefficients(), &[3.0, 4.0]);
+        assert!(history.can_redo());
+        assert_eq!(history.redo(), Some(SCALE));
+        assert_eq!(history.current()[1].coefficients(), &[6.0, 8.0]);
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn applying_forgets_the_undone_operations() {
+        let mut history = matrix().history();
+        history.apply(SWAP);
+        history.undo();
+        history.apply(SCALE);
+        assert!(!history.can_redo());
+        assert_eq!(
+            history.elimination().operations().collect::<Vec<_>>(),
+            [SCALE]
+        );
+    }
+
+    #[test]
+    fn records_pair_every_operation_with_its_matrices() {
+        let mut history = matrix().history();
+        history.apply(SWAP).apply(SCALE);
+        let records = history.records().collect::<Vec<_>>();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].operation, SCALE);
+        assert_eq!(records[1].before[1].coefficients(), &[3.0, 4.0]);
+        assert_eq!(records[1].after[1].coefficients(), &[6.0, 8.0]);
+    }
+
+    #[test]
+    fn failed_operations_leave_the_history_untouched() {
+        let mut history = matrix().history();
+        history.apply(SWAP);
+        history.undo();
+        let invalid = RowOperation::AddTo {
+            line: 1,
+            target_line: 1,
+        };
+        assert_eq!(
+            history.try_apply(invalid).err(),
+            Some(RowOperationError::SameLine(1))
+        );
+        assert!(history.can_redo());
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    #[should_panic(expected = "Line 1 cannot be both source and target")]
+    fn apply_rejects_what_replay_would_reject() {
+        matrix().history().apply(RowOperation::AddTo {
+            line: 1,
+            target_line: 1,
+        });
+    }
+
+    #[test]
+    fn replays_on_matrices_of_another_shape() {
+        let mut history = matrix().history();
+        history.apply(SWAP).apply(SCALE);
+        let other = LineMatrix::new(
+            [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            [1.0, 2.0, 3.0],
+        );
+        let replayed = history.replay(other).unwrap().result();
+        assert_eq!(replayed[1].coefficients(), &[0.0, 2.0, 0.0]);
+        assert_eq!(replayed[2].result(), 1.0);
+        let small = AugmentedMatrix::new([[1.0, 2.0]], [3.0]);
+        assert_eq!(
+            history.replay(small).err(),
+            Some(RowOperationError::InvalidLine(2))
+        );
+    }
+
+    #[test]
+    fn small_divisors_are_reported() {
+        let mut history = LineMatrix::new([[1.0, 2.0], [0.0, 1e-12]], [3.0, 1e-12]).history();
+        history.apply(RowOperation::Div {
+            line: 2,
+            value: 1e-12,
+        });
+        assert_eq!(history.current()[2].coefficients(), &[0.0, 1.0]);
+        let [warning] = history.elimination().warnings.as_slice() else {
+            panic!("expected one warning");
+        };
+        assert_eq!((warning.line, warning.column), (2, 2));
+        assert_eq!(warning.magnitude, 1e-12);
+        history.apply(RowOperation::Div {
+            line: 1,
+            value: 2.0,
+        });
+        assert_eq!(history.elimination().warnings.len(), 1);
+        assert_eq!(
+            history
+                .try_apply(RowOperation::Div {
+                    line: 1,
+                    value: 0.0
+                })
+                .err(),
+            Some(RowOperationError::DivisionByZero)
+        );
+    }
+}
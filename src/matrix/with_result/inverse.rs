@@ -0,0 +1,83 @@
+use super::{elimination::Elimination, error::SingularMatrix, AugmentedMatrix, Line};
+use crate::matrix::scalar::Scalar;
+
+impl<const SIZE: usize, const RESULTS: usize, T: Scalar> AugmentedMatrix<SIZE, SIZE, RESULTS, T> {
+    /// Replaces the result columns with the identity matrix, giving `[A | I]`
+    pub fn with_identity(&self) -> AugmentedMatrix<SIZE, SIZE, SIZE, T> {
+        AugmentedMatrix(
+            std::array::from_fn(|i| {
+                let mut identity = [T::zero(); SIZE];
+                identity[i] = T::one();
+                Line(self.0[i].0, identity)
+            }),
+            self.1,
+        )
+    }
+
+    /// Applies Gauss-Jordan elimination to `[A | I]`
+    ///
+    /// If `A` is invertible the result reads `[I | A⁻¹]`, see [`Elimination::inverse`]
+    pub fn inversion(&self) -> Elimination<SIZE, SIZE, SIZE, T> {
+        self.with_identity().to_reduced_row_echelon()
+    }
+
+    /// Inverts the coefficients using Gauss-Jordan elimination on `[A | I]`
+    pub fn inverse(&self) -> Result<AugmentedMatrix<SIZE, SIZE, 0, T>, SingularMatrix> {
+        self.inversion().inverse()
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Elimination<SIZE, SIZE, SIZE, T> {
+    /// Reads `A⁻¹` out of a result reading `[I | A⁻¹]`
+    ///
+    /// Fails with the first column of the coefficients that does not have a pivot of one on
+    /// the diagonal
+    pub fn inverse(&self) -> Result<AugmentedMatrix<SIZE, SIZE, 0, T>, SingularMatrix> {
+        let result = self.result();
+        if let Some(column) = (1..=SIZE).find(|&i| !(result[i].0[i - 1] - T::one()).is_zero()) {
+            return Err(SingularMatrix { column });
+        }
+        Ok(AugmentedMatrix(result.0.map(|line| Line(line.1, [])), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::{rational::Rational, with_result::LineMatrix};
+
+    #[test]
+    fn with_identity_replaces_the_results() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]).with_identity();
+        assert_eq!(matrix[1].results(), &[1.0, 0.0]);
+        assert_eq!(matrix[2].results(), &[0.0, 1.0]);
+        assert_eq!(matrix[2].coefficients(), &[3.0, 4.0]);
+    }
+
+    #[test]
+    fn inverts_exactly() {
+        let inverse = LineMatrix::from_integers([[2, 1], [5, 3]], [0, 0])
+            .inverse()
+            .unwrap();
+        assert_eq!(inverse[1].coefficients(), &[3, -1].map(Rational::from));
+        assert_eq!(inverse[2].coefficients(), &[-5, 2].map(Rational::from));
+    }
+
+    #[test]
+    fn singular_matrices_name_the_column_without_a_pivot() {
+        let matrix = LineMatrix::new(
+            [[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [1.0, 0.0, 1.0]],
+            [0.0; 3],
+        );
+        assert_eq!(matrix.inverse().err(), Some(SingularMatrix { column: 3 }));
+        let matrix = LineMatrix::new([[0.0, 1.0], [0.0, 2.0]], [0.0; 2]);
+        assert_eq!(matrix.inverse().err(), Some(SingularMatrix { column: 1 }));
+    }
+
+    #[test]
+    fn the_inversion_keeps_every_step() {
+        let inversion = LineMatrix::new([[2.0, 1.0], [5.0, 3.0]], [0.0; 2]).inversion();
+        assert!(inversion.operations().count() > 0);
+        assert!(inversion.to_string().contains("│ 1 0│ 3 -1 │"));
+    }
+}
@@ -0,0 +1,180 @@
+use super::{error::LinearlyDependent, solution::Solution, AugmentedMatrix, Line, LineMatrix};
+use crate::{
+    exporters::excalidraw::{Drawable, Element, ExcalidrawFile},
+    matrix::{draw_labelled, format_entry, scalar::Scalar},
+};
+use std::fmt::{Debug, Display};
+
+/// The `x` minimising `|b - Ax|` for a system that has no exact solution
+#[derive(Clone, Copy)]
+pub struct LeastSquares<const ROWS: usize, const COLS: usize, T = f64> {
+    pub solution: Solution<COLS, T>,
+    /// `r = b - Ax`
+    pub residual: Solution<ROWS, T>,
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> LeastSquares<ROWS, COLS, T> {
+    fn new(matrix: &AugmentedMatrix<ROWS, COLS, 1, T>, solution: [T; COLS]) -> Self {
+        let residual = std::array::from_fn(|i| {
+            let line = &matrix.0[i];
+            line.0
+                .iter()
+                .zip(solution)
+                .fold(line.result(), |acc, (&a, x)| acc - a * x)
+        });
+        Self {
+            solution: Solution::new(solution),
+            residual: Solution::new(residual),
+        }
+    }
+
+    /// The euclidean length `|r|` of the residual
+    pub fn residual_norm(&self) -> f64 {
+        self.residual
+            .values()
+            .iter()
+            .map(|value| value.magnitude().powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> AugmentedMatrix<ROWS, COLS, 1, T> {
+    /// The normal equations `AᵀA x = Aᵀb`, which are solvable whenever the columns of `A` are
+    /// linearly independent
+    pub fn normal_equations(&self) -> LineMatrix<COLS, T> {
+        let dot = |a: usize, b: &dyn Fn(&Line<COLS, 1, T>) -> T| {
+            self.0
+                .iter()
+                .fold(T::zero(), |acc, line| acc + line.0[a] * b(line))
+        };
+        AugmentedMatrix(
+            std::array::from_fn(|i| {
+                Line(
+                    std::array::from_fn(|j| dot(i, &|line| line.0[j])),
+                    [dot(i, &|line| line.result())],
+                )
+            }),
+            self.1,
+        )
+    }
+
+    /// Solves the normal equations using Gauss-Jordan elimination
+    ///
+    /// Returns `None` if the columns of the coefficients are linearly dependent
+    pub fn least_squares(&self) -> Option<LeastSquares<ROWS, COLS, T>> {
+        let solution = self.normal_equations().solve()?;
+        Some(LeastSquares::new(self, solution.into()))
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> AugmentedMatrix<ROWS, COLS> {
+    /// Solves `Rx = Qᵀb` by back substitution, which avoids squaring the condition like the
+    /// normal equations do
+    pub fn least_squares_qr(&self) -> Result<LeastSquares<ROWS, COLS>, LinearlyDependent> {
+        let qr = self.qr()?;
+        let mut x = [0.0; COLS];
+        for i in (0..COLS).rev() {
+            let projected = (0..ROWS)
+                .map(|line| qr.q.0[line].0[i] * self.0[line].result())
+                .sum::<f64>();
+            let known = qr.r.0[i].0[i + 1..]
+                .iter()
+                .zip(&x[i + 1..])
+                .map(|(r, x)| r * x)
+                .sum::<f64>();
+            x[i] = (projected - known) / qr.r.0[i].0[i];
+        }
+        Ok(LeastSquares::new(self, x))
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> Debug for LeastSquares<ROWS, COLS, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> Display for LeastSquares<ROWS, COLS, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        f.write_str("\nx =")?;
+        Display::fmt(&self.solution, f)?;
+        f.write_str("\nr = b - Ax =")?;
+        Display::fmt(&self.residual, f)?;
+        f.write_fmt(format_args!(
+            "\n|r| = {}",
+            format_entry(self.residual_norm(), precision)
+        ))
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> Drawable for LeastSquares<ROWS, COLS, T> {
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        let scale = file.app_state.grid_size;
+        let (width, height) = draw_labelled(
+            file,
+            x,
+            y,
+            locked,
+            &[("x =", &self.solution), ("r =", &self.residual)],
+        );
+        file.elements.push(Element::draw_small_monospaced_text(
+            x,
+            y + height + scale,
+            locked,
+            format!("|r| = {}", format_entry(self.residual_norm(), 3)),
+        ));
+        (width, height + scale * 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::rational::Rational;
+
+    #[test]
+    fn normal_equations_multiply_with_the_transpose() {
+        let normal =
+            AugmentedMatrix::from_integers([[1, 0], [1, 1], [1, 2]], [1, 3, 4]).normal_equations();
+        assert_eq!(normal[1].coefficients(), &[3, 3].map(Rational::from));
+        assert_eq!(normal[2].coefficients(), &[3, 5].map(Rational::from));
+        assert_eq!(normal[2].result(), Rational::from(11));
+    }
+
+    #[test]
+    fn fits_a_line_through_points() {
+        let fit = AugmentedMatrix::from_integers([[1, 0], [1, 1], [1, 2]], [1, 3, 4])
+            .least_squares()
+            .unwrap();
+        assert_eq!(
+            fit.solution.values(),
+            &[Rational::new(7, 6), Rational::new(3, 2)]
+        );
+        assert_eq!(
+            fit.residual.values(),
+            &[
+                Rational::new(-1, 6),
+                Rational::new(1, 3),
+                Rational::new(-1, 6)
+            ]
+        );
+        assert!((fit.residual_norm() - 6f64.sqrt() / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn qr_gives_the_same_fit() {
+        let matrix = AugmentedMatrix::new([[1.0, 0.0], [1.0, 1.0], [1.0, 2.0]], [1.0, 3.0, 4.0]);
+        let fit = matrix.least_squares_qr().unwrap();
+        for (value, expected) in fit.solution.values().iter().zip([7.0 / 6.0, 1.5]) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+        let dependent = AugmentedMatrix::new([[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]], [1.0; 3]);
+        assert!(dependent.least_squares().is_none());
+        assert_eq!(
+            dependent.least_squares_qr().err(),
+            Some(LinearlyDependent { column: 2 })
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use super::{error::SingularMatrix, solution::Solution, AugmentedMatrix};
+use crate::{
+    exporters::excalidraw::{Drawable, ExcalidrawFile},
+    matrix::{draw_labelled, scalar::Scalar},
+};
+use std::fmt::{Debug, Display};
+
+/// The factorisation `PA = LU` of the coefficients
+///
+/// Once factorised, the system can be solved for any result column with two substitutions
+/// instead of a whole elimination
+#[derive(Clone, Copy)]
+pub struct LuDecomposition<const SIZE: usize, T = f64> {
+    /// The permutation of the lines chosen by partial pivoting
+    pub p: AugmentedMatrix<SIZE, SIZE, 0, T>,
+    /// The lower triangular matrix of elimination factors with ones on the diagonal
+    pub l: AugmentedMatrix<SIZE, SIZE, 0, T>,
+    /// The upper triangular matrix left after elimination
+    pub u: AugmentedMatrix<SIZE, SIZE, 0, T>,
+    /// Line `i` of `PA` is line `permutation[i]` of `A`, **0-based**
+    permutation: [usize; SIZE],
+}
+
+impl<const SIZE: usize, const RESULTS: usize, T: Scalar> AugmentedMatrix<SIZE, SIZE, RESULTS, T> {
+    /// Factorises the coefficients into `PA = LU` using partial pivoting
+    ///
+    /// In every column the entry with the largest magnitude is chosen as pivot
+    pub fn lu(&self) -> Result<LuDecomposition<SIZE, T>, SingularMatrix> {
+        let mut u = self.0.map(|line| line.0);
+        let mut l = [[T::zero(); SIZE]; SIZE];
+        let mut permutation = std::array::from_fn(|i| i);
+        for col in 0..SIZE {
+            let pivot_line = (col + 1..SIZE).fold(col, |best, line| {
+                if u[line][col].magnitude() > u[best][col].magnitude() {
+                    line
+                } else {
+                    best
+                }
+            });
+            if u[pivot_line][col].is_zero() {
+                return Err(SingularMatrix { column: col + 1 });
+            }
+            if pivot_line != col {
+                u.swap(col, pivot_line);
+                l.swap(col, pivot_line);
+                permutation.swap(col, pivot_line);
+            }
+            for line in col + 1..SIZE {
+                let factor = u[line][col] / u[col][col];
+                l[line][col] = factor;
+                let pivot = u[col];
+                for (value, &pivot) in u[line].iter_mut().zip(&pivot).skip(col) {
+                    *value -= factor * pivot;
+                }
+            }
+        }
+        for (i, line) in l.iter_mut().enumerate() {
+            line[i] = T::one();
+        }
+        let p = std::array::from_fn(|i| {
+            let mut line = [T::zero(); SIZE];
+            line[permutation[i]] = T::one();
+            line
+        });
+        Ok(LuDecomposition {
+            p: AugmentedMatrix::with_results(p, [[]; SIZE]),
+            l: AugmentedMatrix::with_results(l, [[]; SIZE]),
+            u: AugmentedMatrix::with_results(u, [[]; SIZE]),
+            permutation,
+        })
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> LuDecomposition<SIZE, T> {
+    /// Solves `Ax = result` by forward substitution `Ly = Pb` and back substitution `Ux = y`
+    pub fn solve(&self, result: [T; SIZE]) -> Solution<SIZE, T> {
+        let mut y = [T::zero(); SIZE];
+        for i in 0..SIZE {
+            y[i] = result[self.permutation[i]];
+            for col in 0..i {
+                y[i] -= self.l.0[i].0[col] * y[col];
+            }
+        }
+        let mut x = [T::zero(); SIZE];
+        for i in (0..SIZE).rev() {
+            x[i] = y[i];
+            for col in i + 1..SIZE {
+                x[i] -= self.u.0[i].0[col] * x[col];
+            }
+            x[i] /= self.u.0[i].0[i];
+        }
+        Solution::new(x)
+    }
+
+    /// Solves `AX = B` for every result column at once
+    pub fn solve_all<const RESULTS: usize>(
+        &self,
+        results: [[T; RESULTS]; SIZE],
+    ) -> [Solution<SIZE, T>; RESULTS] {
+        std::array::from_fn(|col| self.solve(results.map(|line| line[col])))
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Debug for LuDecomposition<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Display for LuDecomposition<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, matrix) in [("P", &self.p), ("L", &self.l), ("U", &self.u)] {
+            f.write_fmt(format_args!("\n{name} ="))?;
+            Display::fmt(matrix, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Drawable for LuDecomposition<SIZE, T> {
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        draw_labelled(
+            file,
+            x,
+            y,
+            locked,
+            &[("P =", &self.p), ("L =", &self.l), ("U =", &self.u)],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::{rational::Rational, with_result::LineMatrix};
+
+    fn product<const SIZE: usize, T: Scalar>(
+        a: &AugmentedMatrix<SIZE, SIZE, 0, T>,
+        b: &AugmentedMatrix<SIZE, SIZE, 0, T>,
+    ) -> [[T; SIZE]; SIZE] {
+        std::array::from_fn(|line| {
+            std::array::from_fn(|col| {
+                (0..SIZE).fold(T::zero(), |acc, i| acc + a.0[line].0[i] * b.0[i].0[col])
+            })
+        })
+    }
+
+    #[test]
+    fn factors_multiply_back_to_the_permuted_matrix() {
+        let matrix = LineMatrix::from_integers([[1, 2, 0], [4, 1, 2], [2, 3, 5]], [0; 3]);
+        let lu = matrix.lu().unwrap();
+        let a = AugmentedMatrix::with_results(matrix.0.map(|line| line.0), [[]; 3]);
+        assert_eq!(product(&lu.p, &a), product(&lu.l, &lu.u));
+        for line in 0..3 {
+            assert_eq!(lu.l.0[line].0[line], Rational::ONE);
+            for col in 0..line {
+                assert_eq!(lu.u.0[line].0[col], Rational::ZERO);
+            }
+        }
+    }
+
+    #[test]
+    fn partial_pivoting_picks_the_largest_entry() {
+        let lu = LineMatrix::new([[1.0, 2.0], [4.0, 1.0]], [0.0; 2])
+            .lu()
+            .unwrap();
+        assert_eq!(lu.u.0[0].0, [4.0, 1.0]);
+        assert_eq!(lu.p.0[0].0, [0.0, 1.0]);
+        assert_eq!(lu.l.0[1].0, [0.25, 1.0]);
+    }
+
+    #[test]
+    fn solves_for_several_result_columns() {
+        let lu = LineMatrix::from_integers([[2, 1], [5, 3]], [0, 0])
+            .lu()
+            .unwrap();
+        let [first, second] = lu.solve_all([[1, 0], [0, 1]].map(|line| line.map(Rational::from)));
+        assert_eq!(first.values(), &[3, -5].map(Rational::from));
+        assert_eq!(second.values(), &[-1, 2].map(Rational::from));
+    }
+
+    #[test]
+    fn singular_matrices_name_the_column_without_a_pivot() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [2.0, 4.0]], [0.0; 2]);
+        assert_eq!(matrix.lu().err(), Some(SingularMatrix { column: 2 }));
+    }
+}
@@ -0,0 +1,557 @@
+//! Reading row operations the way they are written by hand
+//!
+//! Lines are roman numerals like `III` or `R3`, factors can be written with `·`, `*` or just a
+//! space and values containing signs or fractions go into parentheses. The changed line is
+//! either assigned with `:=`, `<-` or `=` or it is the first line of the expression:
+//!
+//! - `II - 2·I`, `III := III + 3 I`, `R2 <- R2 - R1 / 2`
+//! - `R2 <- R2 / 4`, `II : 4`, `I · (-1/2)`
+//! - `I <-> III`, `x₁ <-> x₃`
+
+use super::{
+    elimination::RowOperation,
+    error::{ParseOperationError, ParseOperationErrorKind},
+    variable::digit,
+};
+use crate::matrix::scalar::Scalar;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Line(usize),
+    Column(usize),
+    /// The text of a number, without surrounding parentheses
+    Number(String),
+    Plus,
+    Minus,
+    Times,
+    Slash,
+    Assign,
+    Swap,
+}
+
+/// How a line appears in an expression
+enum Factor<T> {
+    One,
+    Mul(T),
+    Div(T),
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    /// Every token with the position of its first character
+    tokens: Vec<(usize, Token)>,
+    index: usize,
+}
+
+impl<T: Scalar + FromStr> FromStr for RowOperation<T> {
+    type Err = ParseOperationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::new(s)?.operation()
+    }
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Result<Self, ParseOperationError> {
+        let error = |position, kind| ParseOperationError {
+            input: input.to_string(),
+            position,
+            kind,
+        };
+        let chars = input.chars().collect::<Vec<_>>();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let start = i;
+            let c = chars[i];
+            i += 1;
+            let token = match c {
+                _ if c.is_whitespace() => continue,
+                '+' => Token::Plus,
+                '-' | '−' => Token::Minus,
+                '·' | '⋅' | '*' | '×' => Token::Times,
+                '/' => Token::Slash,
+                ':' if chars.get(i) != Some(&'=') => Token::Slash,
+                ':' | '=' | '←' => {
+                    i += usize::from(c == ':');
+                    Token::Assign
+                }
+                '↔' => Token::Swap,
+                '<' => match (chars.get(i), chars.get(i + 1)) {
+                    (Some('-'), Some('>')) => {
+                        i += 2;
+                        Token::Swap
+                    }
+                    (Some('-'), _) => {
+                        i += 1;
+                        Token::Assign
+                    }
+                    _ => {
+                        return Err(error(
+                            start,
+                            ParseOperationErrorKind::UnexpectedCharacter(c),
+                        ))
+                    }
+                },
+                '(' => {
+                    let mut depth = 1;
+                    while depth > 0 {
+                        match chars.get(i) {
+                            Some('(') => depth += 1,
+                            Some(')') => depth -= 1,
+                            Some(_) => {}
+                            None => {
+                                return Err(error(
+                                    start,
+                                    ParseOperationErrorKind::UnclosedParenthesis,
+                                ))
+                            }
+                        }
+                        i += 1;
+                    }
+                    Token::Number(chars[start + 1..i - 1].iter().collect())
+                }
+                '0'..='9' | '.' | 'i' => {
+                    if c != 'i' {
+                        while chars
+                            .get(i)
+                            .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+                        {
+                            i += 1;
+                        }
+                        i += usize::from(chars.get(i) == Some(&'i'));
+                    }
+                    Token::Number(chars[start..i].iter().collect())
+                }
+                'I' | 'V' | 'X' | 'L' | 'C' | 'D' | 'M' => {
+                    while chars.get(i).is_some_and(|c| "IVXLCDM".contains(*c)) {
+                        i += 1;
+                    }
+                    let text = chars[start..i].iter().collect::<String>();
+                    match from_roman(&text) {
+                        Some(line) => Token::Line(line),
+                        None => {
+                            return Err(error(start, ParseOperationErrorKind::InvalidLine(text)))
+                        }
+                    }
+                }
+                'R' | 'r' | 'x' => {
+                    let digits = chars[i..]
+                        .iter()
+                        .map_while(|&c| digit(c))
+                        .collect::<Vec<_>>();
+                    i += digits.len();
+                    // Numbers too large for a line or column are as invalid as zero
+                    let number = digits
+                        .iter()
+                        .try_fold(0usize, |acc, &digit| {
+                            acc.checked_mul(10)?.checked_add(digit)
+                        })
+                        .unwrap_or(0);
+                    if number == 0 {
+                        let kind = if c == 'x' {
+                            ParseOperationErrorKind::ExpectedColumn
+                        } else {
+                            ParseOperationErrorKind::InvalidLine(chars[start..i].iter().collect())
+                        };
+                        return Err(error(start, kind));
+                    }
+                    if c == 'x' {
+                        Token::Column(number)
+                    } else {
+                        Token::Line(number)
+                    }
+                }
+                _ => {
+                    return Err(error(
+                        start,
+                        ParseOperationErrorKind::UnexpectedCharacter(c),
+                    ))
+                }
+            };
+            tokens.push((start, token));
+        }
+        Ok(Self {
+            input,
+            tokens,
+            index: 0,
+        })
+    }
+
+    fn error(&self, kind: ParseOperationErrorKind) -> ParseOperationError {
+        let position = match self.tokens.get(self.index) {
+            Some((position, _)) => *position,
+            None => self.input.chars().count(),
+        };
+        ParseOperationError {
+            input: self.input.to_string(),
+            position,
+            kind,
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(_, token)| token)
+    }
+
+    /// Moves past the next token if it is `token`
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek() == Some(token);
+        self.index += usize::from(found);
+        found
+    }
+
+    fn line(&mut self) -> Result<usize, ParseOperationError> {
+        match self.peek() {
+            Some(&Token::Line(line)) => {
+                self.index += 1;
+                Ok(line)
+            }
+            _ => Err(self.error(ParseOperationErrorKind::ExpectedLine)),
+        }
+    }
+
+    fn column(&mut self) -> Result<usize, ParseOperationError> {
+        match self.peek() {
+            Some(&Token::Column(column)) => {
+                self.index += 1;
+                Ok(column)
+            }
+            _ => Err(self.error(ParseOperationErrorKind::ExpectedColumn)),
+        }
+    }
+
+    /// A number, fractions like `(-1/2)` are read as two numbers and divided so they work for
+    /// every scalar type
+    fn number<T: Scalar + FromStr>(&mut self) -> Result<T, ParseOperationError> {
+        let Some(Token::Number(text)) = self.peek() else {
+            return Err(self.error(ParseOperationErrorKind::ExpectedNumber));
+        };
+        let text = text.clone();
+        let parse = |text: &str| text.trim().parse::<T>().ok();
+        let value = match text.split_once('/') {
+            Some((numerator, denominator)) if !denominator.contains('/') => parse(numerator)
+                .zip(parse(denominator).filter(|denominator| !denominator.is_zero()))
+                .map(|(numerator, denominator)| numerator / denominator),
+            Some(_) => None,
+            None => parse(&text),
+        };
+        let value =
+            value.ok_or_else(|| self.error(ParseOperationErrorKind::InvalidNumber(text)))?;
+        self.index += 1;
+        Ok(value)
+    }
+
+    fn end(&self) -> Result<(), ParseOperationError> {
+        match self.peek() {
+            Some(_) => Err(self.error(ParseOperationErrorKind::ExpectedEnd)),
+            None => Ok(()),
+        }
+    }
+
+    /// A line with an optional factor, like `I`, `3·I`, `3 I`, `I·3` or `I / 3`
+    fn term<T: Scalar + FromStr>(&mut self) -> Result<(usize, Factor<T>), ParseOperationError> {
+        if let Some(Token::Number(_)) = self.peek() {
+            let value = self.number()?;
+            self.eat(&Token::Times);
+            return Ok((self.line()?, Factor::Mul(value)));
+        }
+        let line = self.line()?;
+        let factor = if self.eat(&Token::Times) {
+            Factor::Mul(self.number()?)
+        } else if self.eat(&Token::Slash) {
+            Factor::Div(self.number()?)
+        } else {
+            Factor::One
+        };
+        Ok((line, factor))
+    }
+
+    fn operation<T: Scalar + FromStr>(&mut self) -> Result<RowOperation<T>, ParseOperationError> {
+        let head = self
+            .tokens
+            .iter()
+            .take(2)
+            .map(|(_, token)| token.clone())
+            .collect::<Vec<_>>();
+        let target = match head.as_slice() {
+            [] => return Err(self.error(ParseOperationErrorKind::Empty)),
+            [Token::Column(column), Token::Swap] => {
+                self.index = 2;
+                let other_column = self.column()?;
+                self.end()?;
+                return Ok(RowOperation::SwapColumns {
+                    column: *column,
+                    other_column,
+                });
+            }
+            [Token::Line(line), Token::Swap] => {
+                self.index = 2;
+                let other_line = self.line()?;
+                self.end()?;
+                return Ok(RowOperation::Swap {
+                    line: *line,
+                    other_line,
+                });
+            }
+            [Token::Column(_), ..] => return Err(self.error(ParseOperationErrorKind::ExpectedLine)),
+            [Token::Line(line), Token::Assign] => {
+                self.index = 2;
+                Some(*line)
+            }
+            _ => None,
+        };
+        let expression_start = self.index;
+        let first = self.term::<T>()?;
+        let negative = if self.eat(&Token::Plus) {
+            false
+        } else if self.eat(&Token::Minus) {
+            true
+        } else {
+            self.end()?;
+            let (line, factor) = first;
+            self.index = expression_start;
+            if target.is_some_and(|target| target != line) {
+                return Err(self.error(ParseOperationErrorKind::NotASingleOperation));
+            }
+            return match factor {
+                Factor::Mul(value) => Ok(RowOperation::Mul { line, value }),
+                Factor::Div(value) => Ok(RowOperation::Div { line, value }),
+                Factor::One => Err(self.error(ParseOperationErrorKind::NotASingleOperation)),
+            };
+        };
+        let second = self.term::<T>()?;
+        self.end()?;
+        self.index = expression_start;
+        let target_line = target.unwrap_or(first.0);
+        let (line, factor) = match (first, second) {
+            ((first, Factor::One), second) if first == target_line => second,
+            (first, (second, Factor::One)) if second == target_line && !negative => first,
+            ((first, _), (second, _)) if first == target_line || second == target_line => {
+                return Err(self.error(ParseOperationErrorKind::NotASingleOperation))
+            }
+            _ => return Err(self.error(ParseOperationErrorKind::MissingTarget(target_line))),
+        };
+        let sign = |value: T| if negative { -value } else { value };
+        Ok(match factor {
+            Factor::One if negative => RowOperation::SubTo { line, target_line },
+            Factor::One => RowOperation::AddTo { line, target_line },
+            Factor::Mul(value) => RowOperation::MulTo {
+                line,
+                target_line,
+                value: sign(value),
+            },
+            Factor::Div(value) => RowOperation::DivTo {
+                line,
+                target_line,
+                value: sign(value),
+            },
+        })
+    }
+}
+
+/// Reads a roman numeral, only accepting the form [`roman`](super::elimination::roman) writes
+fn from_roman(text: &str) -> Option<usize> {
+    let value = |c| match c {
+        'I' => 1,
+        'V' => 5,
+        'X' => 10,
+        'L' => 50,
+        'C' => 100,
+        'D' => 500,
+        _ => 1000,
+    };
+    let values = text.chars().map(value).collect::<Vec<usize>>();
+    let number = values.iter().enumerate().fold(0, |acc, (i, &v)| {
+        if values.get(i + 1).is_some_and(|&next| next > v) {
+            acc - v as isize
+        } else {
+            acc + v as isize
+        }
+    });
+    let number = usize::try_from(number).ok()?;
+    (super::elimination::roman(number) == text).then_some(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::rational::Rational;
+
+    #[test]
+    fn reads_operations_written_by_hand() {
+        let cases = [
+            (
+                "II - 2·I",
+                RowOperation::MulTo {
+                    line: 1,
+                    target_line: 2,
+                    value: -2.0,
+                },
+            ),
+            (
+                "III := III + 3 I",
+                RowOperation::MulTo {
+                    line: 1,
+                    target_line: 3,
+                    value: 3.0,
+                },
+            ),
+            (
+                "R2 <- R2 - R1 / 2",
+                RowOperation::DivTo {
+                    line: 1,
+                    target_line: 2,
+                    value: -2.0,
+                },
+            ),
+            (
+                "R2 <- R2 / 4",
+                RowOperation::Div {
+                    line: 2,
+                    value: 4.0,
+                },
+            ),
+            (
+                "II : 4",
+                RowOperation::Div {
+                    line: 2,
+                    value: 4.0,
+                },
+            ),
+            (
+                "I · (-1/2)",
+                RowOperation::Mul {
+                    line: 1,
+                    value: -0.5,
+                },
+            ),
+            (
+                "I <-> III",
+                RowOperation::Swap {
+                    line: 1,
+                    other_line: 3,
+                },
+            ),
+            (
+                "x₁ <-> x₃",
+                RowOperation::SwapColumns {
+                    column: 1,
+                    other_column: 3,
+                },
+            ),
+            (
+                "I + II",
+                RowOperation::AddTo {
+                    line: 2,
+                    target_line: 1,
+                },
+            ),
+            (
+                "II = II − I",
+                RowOperation::SubTo {
+                    line: 1,
+                    target_line: 2,
+                },
+            ),
+        ];
+        for (text, operation) in cases {
+            assert_eq!(text.parse(), Ok(operation), "{text}");
+        }
+    }
+
+    #[test]
+    fn the_written_form_is_read_back() {
+        let operations = [
+            RowOperation::MulTo {
+                line: 1,
+                target_line: 2,
+                value: -2.0,
+            },
+            RowOperation::DivTo {
+                line: 4,
+                target_line: 9,
+                value: 3.0,
+            },
+            RowOperation::Mul {
+                line: 3,
+                value: -0.5,
+            },
+        ];
+        for operation in operations {
+            assert_eq!(operation.to_string().parse(), Ok(operation));
+        }
+    }
+
+    #[test]
+    fn fractions_are_divided_for_every_scalar_type() {
+        assert_eq!(
+            "I · (-1/3)".parse(),
+            Ok(RowOperation::Mul {
+                line: 1,
+                value: Rational::new(-1, 3)
+            })
+        );
+        assert_eq!(
+            "I · (1/4)".parse(),
+            Ok(RowOperation::Mul {
+                line: 1,
+                value: 0.25_f32
+            })
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        use ParseOperationErrorKind::*;
+        let cases = [
+            ("", 0, Empty),
+            ("  ", 2, Empty),
+            ("II - 2·", 7, ExpectedLine),
+            ("I + (2", 4, UnclosedParenthesis),
+            ("IIII + I", 0, InvalidLine(String::from("IIII"))),
+            ("R0 + R1", 0, InvalidLine(String::from("R0"))),
+            ("I ? II", 2, UnexpectedCharacter('?')),
+            ("I <-> x1", 6, ExpectedLine),
+            ("x1 + x2", 0, ExpectedLine),
+            ("I · (1/0)", 4, InvalidNumber(String::from("1/0"))),
+            ("I · (1/2/3)", 4, InvalidNumber(String::from("1/2/3"))),
+            ("I · 2 II", 6, ExpectedEnd),
+            ("II := I + III", 6, MissingTarget(2)),
+            ("II := 2·II + I", 6, NotASingleOperation),
+            ("I", 0, NotASingleOperation),
+        ];
+        for (text, position, kind) in cases {
+            let error = text.parse::<RowOperation>().unwrap_err();
+            assert_eq!((error.position, &error.kind), (position, &kind), "{text}");
+        }
+    }
+
+    #[test]
+    fn error_messages_show_the_position() {
+        let error = "I + (2".parse::<RowOperation>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unclosed parenthesis at position 5\nI + (2\n    ^"
+        );
+    }
+
+    #[test]
+    fn numbers_too_large_for_a_line_are_rejected() {
+        let text = "R99999999999999999999999 + R1";
+        let error = text.parse::<RowOperation>().unwrap_err();
+        assert_eq!(error.position, 0);
+        assert_eq!(
+            error.kind,
+            ParseOperationErrorKind::InvalidLine(String::from("R99999999999999999999999"))
+        );
+        let error = "x1 <-> x99999999999999999999999"
+            .parse::<RowOperation>()
+            .unwrap_err();
+        assert_eq!(
+            (error.position, error.kind),
+            (7, ParseOperationErrorKind::ExpectedColumn)
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use super::{
+    error::LinearlyDependent,
+    solution::{subscript, Solution},
+    AugmentedMatrix, Line,
+};
+use crate::{
+    exporters::excalidraw::{Drawable, ExcalidrawFile},
+    matrix::{draw_labelled, format_entry, scalar::Scalar},
+};
+use std::fmt::{Debug, Display};
+
+/// The variant of the Gram–Schmidt process
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GramSchmidt {
+    /// Projects the original column onto every previous `q`
+    Classical,
+    /// Projects the partially orthogonalised column instead, which loses less precision
+    Modified,
+}
+
+/// A single step of the Gram–Schmidt process, columns are **1-based**
+#[derive(Clone, Copy)]
+pub enum GramSchmidtStep<const ROWS: usize> {
+    /// `v_column := v_column - factor · q_onto`
+    Projection {
+        column: usize,
+        onto: usize,
+        factor: f64,
+        vector: Solution<ROWS>,
+    },
+    /// `q_column := v_column / norm`
+    Normalization {
+        column: usize,
+        norm: f64,
+        vector: Solution<ROWS>,
+    },
+}
+
+/// The factorisation `A = QR` of the coefficients together with every step taken
+#[derive(Clone)]
+pub struct QrDecomposition<const ROWS: usize, const COLS: usize> {
+    /// The orthonormal columns
+    pub q: AugmentedMatrix<ROWS, COLS, 0>,
+    /// The upper triangular matrix of projection factors and norms
+    pub r: AugmentedMatrix<COLS, COLS, 0>,
+    pub steps: Vec<GramSchmidtStep<ROWS>>,
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize>
+    AugmentedMatrix<ROWS, COLS, RESULTS>
+{
+    /// Orthonormalises the columns of the coefficients using the Gram–Schmidt process
+    pub fn gram_schmidt(
+        &self,
+        method: GramSchmidt,
+    ) -> Result<QrDecomposition<ROWS, COLS>, LinearlyDependent> {
+        let columns = self.transpose().0;
+        let mut q = [Line([0.0; ROWS], []); COLS];
+        let mut r = [[0.0; COLS]; COLS];
+        let mut steps = Vec::new();
+        for (k, column) in columns.iter().enumerate() {
+            let mut v = *column;
+            for j in 0..k {
+                let factor = match method {
+                    GramSchmidt::Classical => q[j].dot(column),
+                    GramSchmidt::Modified => q[j].dot(&v),
+                };
+                r[j][k] = factor;
+                v -= q[j] * factor;
+                steps.push(GramSchmidtStep::Projection {
+                    column: k + 1,
+                    onto: j + 1,
+                    factor,
+                    vector: Solution::new(v.0),
+                });
+            }
+            let norm = v.norm();
+            if norm.is_zero() {
+                return Err(LinearlyDependent { column: k + 1 });
+            }
+            r[k][k] = norm;
+            q[k] = v / norm;
+            steps.push(GramSchmidtStep::Normalization {
+                column: k + 1,
+                norm,
+                vector: Solution::new(q[k].0),
+            });
+        }
+        Ok(QrDecomposition {
+            q: AugmentedMatrix(q, None).transpose(),
+            r: AugmentedMatrix::with_results(r, [[]; COLS]),
+            steps,
+        })
+    }
+
+    /// Factorises the coefficients into `A = QR` using modified Gram–Schmidt
+    pub fn qr(&self) -> Result<QrDecomposition<ROWS, COLS>, LinearlyDependent> {
+        self.gram_schmidt(GramSchmidt::Modified)
+    }
+}
+
+impl<const ROWS: usize> Debug for GramSchmidtStep<ROWS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const ROWS: usize> Display for GramSchmidtStep<ROWS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        match *self {
+            Self::Projection {
+                column,
+                onto,
+                factor,
+                vector,
+            } => {
+                let column = subscript(column);
+                f.write_fmt(format_args!(
+                    "v{column} := v{column} {} {}·q{}",
+                    if factor < 0.0 { '+' } else { '-' },
+                    format_entry(factor.abs(), precision),
+                    subscript(onto)
+                ))?;
+                Display::fmt(&vector, f)
+            }
+            Self::Normalization {
+                column,
+                norm,
+                vector,
+            } => {
+                let column = subscript(column);
+                f.write_fmt(format_args!(
+                    "q{column} := v{column} / {}",
+                    format_entry(norm, precision)
+                ))?;
+                Display::fmt(&vector, f)
+            }
+        }
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> Debug for QrDecomposition<ROWS, COLS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> Display for QrDecomposition<ROWS, COLS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            f.write_str("\n")?;
+            Display::fmt(step, f)?;
+        }
+        f.write_str("\n\nQ =")?;
+        Display::fmt(&self.q, f)?;
+        f.write_str("\nR =")?;
+        Display::fmt(&self.r, f)
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> Drawable for QrDecomposition<ROWS, COLS> {
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        draw_labelled(file, x, y, locked, &[("Q =", &self.q), ("R =", &self.r)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!((value - expected).abs() < 1e-12, "{value} != {expected}");
+    }
+
+    #[test]
+    fn q_is_orthonormal_and_qr_gives_back_the_matrix() {
+        let matrix = AugmentedMatrix::new([[3.0, 1.0], [4.0, 2.0], [0.0, 2.0]], [0.0; 3]);
+        for method in [GramSchmidt::Classical, GramSchmidt::Modified] {
+            let qr = matrix.gram_schmidt(method).unwrap();
+            let columns = qr.q.transpose().0;
+            for (i, q) in columns.iter().enumerate() {
+                for (j, other) in columns.iter().enumerate() {
+                    assert_close(q.dot(other), f64::from(i == j));
+                }
+            }
+            for line in 0..3 {
+                for col in 0..2 {
+                    let value = (0..2).map(|k| qr.q.0[line].0[k] * qr.r.0[k].0[col]).sum();
+                    assert_close(value, matrix.0[line].0[col]);
+                }
+            }
+            assert_close(qr.r.0[1].0[0], 0.0);
+        }
+    }
+
+    #[test]
+    fn records_every_projection_and_normalization() {
+        let qr = AugmentedMatrix::new([[3.0, 1.0], [4.0, 2.0]], [0.0; 2])
+            .qr()
+            .unwrap();
+        assert!(matches!(
+            qr.steps.as_slice(),
+            [
+                GramSchmidtStep::Normalization { column: 1, .. },
+                GramSchmidtStep::Projection {
+                    column: 2,
+                    onto: 1,
+                    ..
+                },
+                GramSchmidtStep::Normalization { column: 2, .. },
+            ]
+        ));
+        assert_eq!(qr.steps[0].to_string().lines().next(), Some("q₁ := v₁ / 5"));
+        assert_eq!(
+            qr.steps[1].to_string().lines().next(),
+            Some("v₂ := v₂ - 2.2·q₁")
+        );
+    }
+
+    #[test]
+    fn dependent_columns_are_rejected() {
+        let matrix = AugmentedMatrix::new([[1.0, 2.0], [2.0, 4.0]], [0.0; 2]);
+        assert_eq!(matrix.qr().err(), Some(LinearlyDependent { column: 2 }));
+    }
+}
@@ -0,0 +1,503 @@
+use super::{elimination::Pivoting, variable::Variable, AugmentedMatrix};
+use crate::{
+    exporters::excalidraw::{Drawable, ExcalidrawFile},
+    matrix::{draw_augmented, draw_text_lines, fmt_augmented, scalar::Scalar},
+};
+use std::{
+    fmt::{Debug, Display},
+    ops::Index,
+};
+
+/// The values of all variables of a uniquely solvable system
+///
+/// Solutions of a matrix with [labels](AugmentedMatrix::with_labels) are written as `x = 1`
+/// instead of a vector
+///
+/// **Indexing is 1-based**
+#[derive(Clone, Copy, PartialEq)]
+pub struct Solution<const SIZE: usize, T = f64>([T; SIZE], Option<[Variable; SIZE]>);
+
+impl<const SIZE: usize, T: Scalar> Solution<SIZE, T> {
+    pub fn new(values: [T; SIZE]) -> Self {
+        Self(values, None)
+    }
+
+    pub fn values(&self) -> &[T; SIZE] {
+        &self.0
+    }
+
+    pub fn with_labels(mut self, labels: [Variable; SIZE]) -> Self {
+        self.1 = Some(labels);
+        self
+    }
+
+    pub fn labels(&self) -> Option<&[Variable; SIZE]> {
+        self.1.as_ref()
+    }
+
+    /// `x = 1` for every variable, the labels padded to the same width
+    fn equations(&self, labels: &[Variable; SIZE], precision: usize) -> Vec<String> {
+        let labels = labels.map(|label| label.to_string());
+        let width = labels
+            .iter()
+            .map(|label| label.chars().count())
+            .max()
+            .unwrap_or(0);
+        labels
+            .iter()
+            .zip(self.0)
+            .map(|(label, value)| format!("{label:<width$} = {}", value.format(precision)))
+            .collect()
+    }
+
+    fn cells(&self, precision: usize) -> Vec<Vec<String>> {
+        self.0
+            .iter()
+            .map(|&value| vec![value.format(precision)])
+            .collect()
+    }
+}
+
+impl<const SIZE: usize, T> From<Solution<SIZE, T>> for [T; SIZE] {
+    fn from(solution: Solution<SIZE, T>) -> Self {
+        solution.0
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Index<usize> for Solution<SIZE, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        index
+            .checked_sub(1)
+            .and_then(|index| self.0.get(index))
+            .expect("Invalid line")
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Debug for Solution<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Display for Solution<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        match &self.1 {
+            Some(labels) => {
+                for equation in self.equations(labels, precision) {
+                    f.write_fmt(format_args!("\n{equation}"))?;
+                }
+                Ok(())
+            }
+            None => fmt_augmented(f, &self.cells(precision), 1),
+        }
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Drawable for Solution<SIZE, T> {
+    fn draw(&self, file: &mut ExcalidrawFile, x: i32, y: i32, locked: bool) -> (i32, i32) {
+        match &self.1 {
+            Some(labels) => draw_text_lines(file, x, y, locked, self.equations(labels, 3)),
+            None => draw_augmented(file, x, y, locked, &self.cells(3), 1),
+        }
+    }
+}
+
+/// Every solution of an underdetermined system
+///
+/// The solutions are `particular + Σ t · direction` for arbitrary values `t` of the free
+/// variables
+#[derive(Clone, PartialEq)]
+pub struct ParametricSolution<const SIZE: usize, T = f64> {
+    /// The solution with every free variable set to zero
+    pub particular: Solution<SIZE, T>,
+    /// The direction vector of every free variable
+    pub directions: Vec<FreeVariable<SIZE, T>>,
+}
+
+/// A variable that can take any value in an underdetermined system
+#[derive(Clone, Copy, PartialEq)]
+pub struct FreeVariable<const SIZE: usize, T = f64> {
+    /// The column of the variable, **1-based**
+    pub column: usize,
+    /// The change of the solution per unit of the variable
+    pub direction: Solution<SIZE, T>,
+}
+
+impl<const SIZE: usize, T: Scalar> Debug for ParametricSolution<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Display for ParametricSolution<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        fmt_augmented(f, &self.particular.cells(precision), 1)?;
+        for free in &self.directions {
+            let name = match self.particular.labels() {
+                Some(labels) => labels[free.column - 1].to_string(),
+                None => format!("x{}", subscript(free.column)),
+            };
+            f.write_fmt(format_args!("\n+ {name} ·"))?;
+            fmt_augmented(f, &free.direction.cells(precision), 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// The classification of a linear system by its number of solutions
+#[derive(Clone, PartialEq)]
+pub enum SolutionSet<const SIZE: usize, T = f64> {
+    /// The system is inconsistent, `line` reads `0 … 0 | result` with `result ≠ 0` after
+    /// elimination
+    None {
+        line: usize,
+        result: T,
+    },
+    Unique(Solution<SIZE, T>),
+    Infinite(ParametricSolution<SIZE, T>),
+}
+
+impl<const SIZE: usize, T: Scalar> Debug for SolutionSet<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl<const SIZE: usize, T: Scalar> Display for SolutionSet<SIZE, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None { line, result } => f.write_fmt(format_args!(
+                "No solution, line {} reads 0 = {}",
+                super::elimination::roman(*line),
+                result.format(f.precision().unwrap_or(3))
+            )),
+            Self::Unique(solution) => Display::fmt(solution, f),
+            Self::Infinite(solution) => Display::fmt(solution, f),
+        }
+    }
+}
+
+/// Writes a number with subscript digits, like in `x₁`
+pub(crate) fn subscript(number: usize) -> String {
+    number
+        .to_string()
+        .chars()
+        .map(|digit| match digit {
+            '0' => '₀',
+            '1' => '₁',
+            '2' => '₂',
+            '3' => '₃',
+            '4' => '₄',
+            '5' => '₅',
+            '6' => '₆',
+            '7' => '₇',
+            '8' => '₈',
+            _ => '₉',
+        })
+        .collect()
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    AugmentedMatrix<ROWS, COLS, RESULTS, T>
+{
+    /// Reads one solution out of every result column
+    ///
+    /// Returns `None` unless the coefficients form the identity matrix followed by lines
+    /// reading `0 = 0`, i.e. the matrix is in reduced row echelon form and the system is
+    /// uniquely solvable for every result column
+    pub fn solutions(&self) -> Option<[Solution<COLS, T>; RESULTS]> {
+        if ROWS < COLS {
+            return None;
+        }
+        let zero_lines = COLS + 1..=ROWS;
+        if !zero_lines.into_iter().all(|line| {
+            self[line]
+                .coefficients()
+                .iter()
+                .chain(self[line].results())
+                .all(|&x| x.is_zero())
+        }) {
+            return None;
+        }
+        for i in 0..COLS {
+            let is_unit = self[i + 1]
+                .coefficients()
+                .iter()
+                .enumerate()
+                .all(|(col, &x)| {
+                    if col == i {
+                        (x - T::one()).is_zero()
+                    } else {
+                        x.is_zero()
+                    }
+                });
+            if !is_unit {
+                return None;
+            }
+        }
+        Some(std::array::from_fn(|result| {
+            Solution(
+                std::array::from_fn(|i| self[i + 1].results()[result]),
+                self.1,
+            )
+        }))
+    }
+
+    /// Solves `AX = B` for every result column at once using Gauss-Jordan elimination
+    pub fn solve_all(self) -> Option<[Solution<COLS, T>; RESULTS]> {
+        self.to_reduced_row_echelon().result().solutions()
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    AugmentedMatrix<ROWS, COLS, RESULTS, T>
+{
+    /// The direction of every free column of a matrix in reduced row echelon form
+    pub(super) fn free_variables(
+        &self,
+        pivots: &[Option<usize>; COLS],
+    ) -> Vec<FreeVariable<COLS, T>> {
+        (0..COLS)
+            .filter(|&col| pivots[col].is_none())
+            .map(|free| {
+                let mut direction = [T::zero(); COLS];
+                direction[free] = T::one();
+                for (col, pivot) in pivots.iter().enumerate() {
+                    if let Some(line) = pivot {
+                        direction[col] = -self[*line].coefficients()[free];
+                    }
+                }
+                FreeVariable {
+                    column: free + 1,
+                    direction: Solution(direction, self.1),
+                }
+            })
+            .collect()
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, T: Scalar> AugmentedMatrix<ROWS, COLS, 1, T> {
+    /// Reads the solution out of the result column
+    ///
+    /// See [`AugmentedMatrix::solutions`]
+    pub fn solution(&self) -> Option<Solution<COLS, T>> {
+        self.solutions().map(|[solution]| solution)
+    }
+
+    /// Solves the system using Gauss-Jordan elimination
+    pub fn solve(self) -> Option<Solution<COLS, T>> {
+        self.solve_with(Pivoting::None)
+    }
+
+    /// Solves the system using Gauss-Jordan elimination, choosing every pivot with `pivoting`
+    pub fn solve_with(self, pivoting: Pivoting) -> Option<Solution<COLS, T>> {
+        self.to_reduced_row_echelon_with(pivoting)
+            .result()
+            .solution()
+    }
+
+    /// Classifies the system as inconsistent, uniquely solvable or underdetermined
+    ///
+    /// Underdetermined systems are described by a particular solution and one direction per
+    /// free column
+    pub fn solution_set(self) -> SolutionSet<COLS, T> {
+        self.solution_set_with(Pivoting::None)
+    }
+
+    /// Classifies the system like [`Self::solution_set`], choosing every pivot with `pivoting`
+    pub fn solution_set_with(self, pivoting: Pivoting) -> SolutionSet<COLS, T> {
+        let reduced = self.to_reduced_row_echelon_with(pivoting).result();
+        if let Some(line) = (1..=ROWS).find(|&line| {
+            reduced[line]
+                .coefficients()
+                .iter()
+                .all(|value| value.is_zero())
+                && !reduced[line].result().is_zero()
+        }) {
+            return SolutionSet::None {
+                line,
+                result: reduced[line].result(),
+            };
+        }
+        let pivots = reduced.pivot_lines();
+        let mut particular = [T::zero(); COLS];
+        for (col, pivot) in pivots.iter().enumerate() {
+            if let Some(line) = pivot {
+                particular[col] = reduced[*line].result();
+            }
+        }
+        let directions = reduced.free_variables(&pivots);
+        if directions.is_empty() {
+            SolutionSet::Unique(Solution(particular, reduced.1))
+        } else {
+            SolutionSet::Infinite(ParametricSolution {
+                particular: Solution(particular, reduced.1),
+                directions,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::with_result::LineMatrix;
+
+    fn assert_close<const SIZE: usize>(solution: Solution<SIZE>, expected: [f64; SIZE]) {
+        for (value, expected) in solution.values().iter().zip(expected) {
+            assert!((value - expected).abs() < 1e-9, "{value} != {expected}");
+        }
+    }
+
+    #[test]
+    fn solve_reads_the_result_column() {
+        let matrix = LineMatrix::new(
+            [[1.0, 2.0, 1.0], [2.0, 5.0, 3.0], [1.0, 4.0, 6.0]],
+            [4.0, 11.0, 17.0],
+        );
+        let solution = matrix.solve().unwrap();
+        assert_close(solution, [1.0 / 3.0, 2.0 / 3.0, 7.0 / 3.0]);
+        assert_eq!(solution[1], solution.values()[0]);
+    }
+
+    #[test]
+    fn solution_needs_reduced_row_echelon_form() {
+        let matrix = LineMatrix::new([[2.0, 1.0], [0.0, 1.0]], [3.0, 1.0]);
+        assert!(matrix.solution().is_none());
+        assert_close(matrix.solve().unwrap(), [1.0, 1.0]);
+    }
+
+    #[test]
+    fn singular_systems_have_no_unique_solution() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [2.0, 4.0]], [3.0, 6.0]);
+        assert!(matrix.solve().is_none());
+    }
+
+    #[test]
+    fn inconsistent_systems_name_the_contradicting_line() {
+        let set = LineMatrix::new([[1.0, 2.0], [2.0, 4.0]], [3.0, 7.0]).solution_set();
+        assert_eq!(
+            set,
+            SolutionSet::None {
+                line: 2,
+                result: 1.0
+            }
+        );
+        assert_eq!(set.to_string(), "No solution, line II reads 0 = 1");
+    }
+
+    #[test]
+    fn uniquely_solvable_systems_have_one_solution() {
+        let set = LineMatrix::new([[2.0, 1.0], [0.0, 1.0]], [3.0, 1.0]).solution_set();
+        assert_eq!(set, SolutionSet::Unique(Solution::new([1.0, 1.0])));
+    }
+
+    #[test]
+    fn underdetermined_systems_are_described_parametrically() {
+        let set = LineMatrix::new([[1.0, 2.0], [2.0, 4.0]], [3.0, 6.0]).solution_set();
+        let SolutionSet::Infinite(solution) = set else {
+            panic!("expected infinitely many solutions, got {set}");
+        };
+        assert_eq!(solution.particular, Solution::new([3.0, 0.0]));
+        let [free] = solution.directions.as_slice() else {
+            panic!("expected one free variable");
+        };
+        assert_eq!(free.column, 2);
+        assert_eq!(free.direction, Solution::new([-2.0, 1.0]));
+    }
+
+    #[test]
+    fn overdetermined_systems_are_solved_when_the_extra_lines_agree() {
+        let matrix = AugmentedMatrix::new([[1.0, 1.0], [1.0, -1.0], [2.0, 0.0]], [3.0, 1.0, 4.0]);
+        assert_close(matrix.solve().unwrap(), [2.0, 1.0]);
+        let matrix = AugmentedMatrix::new([[1.0, 1.0], [1.0, -1.0], [2.0, 0.0]], [3.0, 1.0, 5.0]);
+        assert!(matrix.solve().is_none());
+    }
+
+    #[test]
+    fn systems_with_more_unknowns_than_equations_have_no_unique_solution() {
+        let matrix = AugmentedMatrix::new([[1.0, 0.0, 1.0], [0.0, 1.0, 1.0]], [1.0, 2.0]);
+        assert!(matrix.solve().is_none());
+    }
+
+    #[test]
+    fn solve_all_solves_every_result_column() {
+        let matrix =
+            AugmentedMatrix::with_results([[1.0, 2.0], [3.0, 4.0]], [[1.0, 0.0], [0.0, 1.0]]);
+        let [first, second] = matrix.solve_all().unwrap();
+        assert_close(first, [-2.0, 1.5]);
+        assert_close(second, [1.0, -0.5]);
+        let singular =
+            AugmentedMatrix::with_results([[1.0, 2.0], [2.0, 4.0]], [[1.0, 0.0], [0.0, 1.0]]);
+        assert!(singular.solve_all().is_none());
+    }
+
+    #[test]
+    fn every_pivoting_strategy_gives_the_same_solution_set() {
+        let matrix = LineMatrix::new(
+            [[1e-3, 1.0, 2.0], [2.0, 2.0, 4.0], [1.0, 0.0, 0.0]],
+            [3.0, 6.0, 0.0],
+        );
+        let expected = matrix.solution_set();
+        for pivoting in [
+            Pivoting::Partial,
+            Pivoting::Complete,
+            Pivoting::ScaledPartial,
+        ] {
+            let SolutionSet::Infinite(solution) = matrix.solution_set_with(pivoting) else {
+                panic!("expected infinitely many solutions with {pivoting}");
+            };
+            let SolutionSet::Infinite(expected) = &expected else {
+                panic!("expected infinitely many solutions");
+            };
+            assert_eq!(solution.particular, expected.particular);
+            assert_eq!(solution.directions[0].column, expected.directions[0].column);
+        }
+        assert_close(
+            LineMatrix::new([[1e-3, 1.0], [1.0, 1.0]], [1.0, 2.0])
+                .solve_with(Pivoting::Partial)
+                .unwrap(),
+            [1000.0 / 999.0, 998.0 / 999.0],
+        );
+    }
+
+    #[test]
+    fn labelled_solutions_are_written_as_equations() {
+        let labels = [Variable::indexed('x', 10), Variable::new('y')];
+        let matrix = LineMatrix::new([[1.0, 1.0], [1.0, -1.0]], [3.0, 1.0]).with_labels(labels);
+        let solution = matrix.solve().unwrap();
+        assert_eq!(solution.labels(), Some(&labels));
+        assert_eq!(solution.to_string(), "\nx₁₀ = 2\ny   = 1");
+        assert_eq!(Solution::new([2.0, 1.0]).with_labels(labels), solution);
+    }
+
+    #[test]
+    fn free_variables_are_named_by_their_label() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [2.0, 4.0]], [3.0, 6.0]);
+        let unlabelled = matrix.solution_set().to_string();
+        let labelled = matrix
+            .with_labels([Variable::new('a'), Variable::new('b')])
+            .solution_set()
+            .to_string();
+        let direction = "\n┌╴  ╶┐\n│ -2 │\n│  1 │\n└╴  ╶┘";
+        let particular = "\n┌╴ ╶┐\n│ 3 │\n│ 0 │\n└╴ ╶┘";
+        assert_eq!(unlabelled, format!("{particular}\n+ x₂ ·{direction}"));
+        assert_eq!(labelled, format!("{particular}\n+ b ·{direction}"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid line")]
+    fn index_zero_is_invalid() {
+        let _ = Solution::new([1.0, 2.0])[0];
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid line")]
+    fn index_past_the_end_is_invalid() {
+        let _ = Solution::new([1.0, 2.0])[3];
+    }
+}
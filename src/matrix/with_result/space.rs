@@ -0,0 +1,96 @@
+use super::{elimination::zero_tolerance, solution::Solution, AugmentedMatrix};
+use crate::matrix::scalar::Scalar;
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    AugmentedMatrix<ROWS, COLS, RESULTS, T>
+{
+    /// The line holding the pivot of every column, `None` for free columns
+    ///
+    /// The matrix has to be in row echelon form, entries that are negligible next to the
+    /// largest coefficient do not count as pivots
+    pub(super) fn pivot_lines(&self) -> [Option<usize>; COLS] {
+        let tolerance = zero_tolerance(self);
+        let mut pivots = [None; COLS];
+        for line in 1..=ROWS {
+            let position = self[line]
+                .0
+                .iter()
+                .position(|value| !value.is_negligible(tolerance));
+            if let Some(col) = position {
+                pivots[col] = Some(line);
+            }
+        }
+        pivots
+    }
+
+    /// The number of linearly independent lines of the coefficients
+    pub fn rank(&self) -> usize {
+        self.to_row_echelon()
+            .result()
+            .pivot_lines()
+            .iter()
+            .flatten()
+            .count()
+    }
+
+    /// A basis of all `x` with `Ax = 0`, one vector per free column
+    ///
+    /// Empty if the columns are linearly independent
+    pub fn null_space(&self) -> Vec<Solution<COLS, T>> {
+        let reduced = self.to_reduced_row_echelon().result();
+        reduced
+            .free_variables(&reduced.pivot_lines())
+            .into_iter()
+            .map(|free| free.direction)
+            .collect()
+    }
+
+    /// A basis of all `Ax`, made of the columns of the coefficients that hold a pivot
+    pub fn column_space(&self) -> Vec<Solution<ROWS, T>> {
+        let pivots = self.to_row_echelon().result().pivot_lines();
+        (0..COLS)
+            .filter(|&col| pivots[col].is_some())
+            .map(|col| Solution::new(std::array::from_fn(|i| self.0[i].0[col])))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::{rational::Rational, with_result::LineMatrix};
+
+    #[test]
+    fn rank_counts_the_independent_lines() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [0.0; 2]);
+        assert_eq!(matrix.rank(), 2);
+        let matrix = AugmentedMatrix::new([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0]], [0.0; 2]);
+        assert_eq!(matrix.rank(), 1);
+        assert_eq!(LineMatrix::new([[0.0; 3]; 3], [0.0; 3]).rank(), 0);
+    }
+
+    #[test]
+    fn null_space_has_one_direction_per_free_column() {
+        let matrix = AugmentedMatrix::from_integers([[1, 2, 3], [2, 4, 6]], [0, 0]);
+        let null_space = matrix.null_space();
+        assert_eq!(
+            null_space
+                .iter()
+                .map(|vector| *vector.values())
+                .collect::<Vec<_>>(),
+            [[-2, 1, 0], [-3, 0, 1]].map(|values| values.map(Rational::from))
+        );
+        assert!(LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [0.0; 2])
+            .null_space()
+            .is_empty());
+    }
+
+    #[test]
+    fn column_space_takes_the_pivot_columns_of_the_original_matrix() {
+        let matrix = AugmentedMatrix::new([[1.0, 2.0, 1.0], [2.0, 4.0, 0.0]], [0.0; 2]);
+        let column_space = matrix.column_space();
+        assert_eq!(column_space.len(), 2);
+        assert_eq!(column_space[0].values(), &[1.0, 2.0]);
+        assert_eq!(column_space[1].values(), &[1.0, 0.0]);
+    }
+}
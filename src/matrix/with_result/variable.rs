@@ -0,0 +1,181 @@
+use super::{solution::subscript, AugmentedMatrix};
+use crate::matrix::scalar::Scalar;
+use std::fmt::{Debug, Display};
+
+/// The name of a variable, a letter with an optional index like `x` or `x₂`
+///
+/// Variables are ordered by their letter and then by their index, `x` comes before `x₁`
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Variable {
+    pub letter: char,
+    pub index: Option<usize>,
+}
+
+impl Variable {
+    pub fn new(letter: char) -> Self {
+        Self {
+            letter,
+            index: None,
+        }
+    }
+
+    pub fn indexed(letter: char, index: usize) -> Self {
+        Self {
+            letter,
+            index: Some(index),
+        }
+    }
+
+    /// `x`, `y` and `z` for up to three variables, `x₁` to `xₙ` for more
+    pub fn defaults<const SIZE: usize>() -> [Self; SIZE] {
+        std::array::from_fn(|i| {
+            if SIZE <= 3 {
+                Self::new(['x', 'y', 'z'][i])
+            } else {
+                Self::indexed('x', i + 1)
+            }
+        })
+    }
+}
+
+impl Debug for Variable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+impl Display for Variable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.index {
+            Some(index) => f.write_fmt(format_args!("{}{}", self.letter, subscript(index))),
+            None => f.write_fmt(format_args!("{}", self.letter)),
+        }
+    }
+}
+
+/// The value of an ascii or subscript digit, used to read indices like `x2` or `x₂`
+pub(crate) fn digit(c: char) -> Option<usize> {
+    match c {
+        '0'..='9' => Some(c as usize - '0' as usize),
+        '₀'..='₉' => Some(c as usize - '₀' as usize),
+        _ => None,
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize, const RESULTS: usize, T: Scalar>
+    AugmentedMatrix<ROWS, COLS, RESULTS, T>
+{
+    /// Labels every coefficient column with its variable
+    ///
+    /// Row operations keep the labels and column swaps swap them. They are shown above the
+    /// matrix and carried over to its solutions
+    pub fn with_labels(mut self, labels: [Variable; COLS]) -> Self {
+        self.1 = Some(labels);
+        self
+    }
+
+    /// Labels the columns with [`Variable::defaults`]
+    pub fn with_default_labels(self) -> Self {
+        self.with_labels(Variable::defaults())
+    }
+
+    pub fn without_labels(mut self) -> Self {
+        self.1 = None;
+        self
+    }
+
+    pub fn labels(&self) -> Option<&[Variable; COLS]> {
+        self.1.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::with_result::{elimination::RowOperation, LineMatrix};
+
+    #[test]
+    fn reads_ascii_and_subscript_digits() {
+        assert_eq!(digit('7'), Some(7));
+        assert_eq!(digit('₀'), Some(0));
+        assert_eq!(digit('₉'), Some(9));
+        assert_eq!(digit('x'), None);
+        assert_eq!(digit('²'), None);
+    }
+
+    #[test]
+    fn defaults_use_letters_for_small_systems() {
+        assert_eq!(
+            Variable::defaults::<3>(),
+            ['x', 'y', 'z'].map(Variable::new)
+        );
+        assert_eq!(
+            Variable::defaults::<4>(),
+            [1, 2, 3, 4].map(|index| Variable::indexed('x', index))
+        );
+    }
+
+    #[test]
+    fn indices_are_written_as_subscripts() {
+        assert_eq!(Variable::new('y').to_string(), "y");
+        assert_eq!(Variable::indexed('x', 12).to_string(), "x₁₂");
+        assert_eq!(format!("{:?}", Variable::indexed('a', 0)), "a₀");
+    }
+
+    #[test]
+    fn orders_by_letter_then_index() {
+        let mut variables = vec![
+            Variable::indexed('x', 10),
+            Variable::new('y'),
+            Variable::indexed('x', 2),
+            Variable::new('x'),
+        ];
+        variables.sort();
+        assert_eq!(
+            variables,
+            [
+                Variable::new('x'),
+                Variable::indexed('x', 2),
+                Variable::indexed('x', 10),
+                Variable::new('y'),
+            ]
+        );
+    }
+
+    #[test]
+    fn matrices_keep_their_labels() {
+        let matrix = LineMatrix::new([[1.0, 2.0], [3.0, 4.0]], [5.0, 6.0]);
+        assert_eq!(matrix.labels(), None);
+        let labelled = matrix.with_default_labels();
+        assert_eq!(labelled.labels(), Some(&Variable::defaults()));
+        let changed = labelled
+            .apply(RowOperation::MulTo {
+                line: 1,
+                target_line: 2,
+                value: -3.0,
+            })
+            .apply(RowOperation::SwapColumns {
+                column: 1,
+                other_column: 2,
+            });
+        assert_eq!(
+            changed.labels(),
+            Some(&[Variable::new('y'), Variable::new('x')])
+        );
+        assert_eq!(changed.without_labels().labels(), None);
+    }
+
+    #[test]
+    fn labels_are_shown_above_the_columns() {
+        let matrix = LineMatrix::new([[1.0, 20.0], [3.0, 4.0]], [5.0, 6.0]);
+        let labelled = matrix.with_labels([Variable::indexed('x', 1), Variable::indexed('x', 2)]);
+        assert_eq!(
+            labelled.to_string(),
+            "\n  x₁ x₂\n┌╴       ╶┐\n│  1 20│5 │\n│  3  4│6 │\n└╴       ╶┘"
+        );
+        assert_eq!(
+            labelled.without_labels().to_string(),
+            "\n┌╴      ╶┐\n│ 1 20│5 │\n│ 3  4│6 │\n└╴      ╶┘"
+        );
+    }
+}